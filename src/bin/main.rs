@@ -1,10 +1,15 @@
 use no_keywords_language::{
-    binding::{bind_file, BoundNode, CommonTypes, Type},
-    nodes::Nodes,
+    binding::{bind_file, BoundNode, Builtin, CommonTypes, Type},
+    diagnostics::Diagnostic,
+    eval::{Interpreter, Value},
+    nodes::{NodeID, Nodes},
     parsing::parse_file,
     tokens::{GetLocation, Lexer, SourceLocation},
 };
-use std::{collections::HashMap, io::Write};
+use std::{
+    collections::HashMap,
+    io::{IsTerminal, Write},
+};
 
 fn help(program_name: &str, f: &mut dyn Write) -> std::io::Result<()> {
     writeln!(f, "Usage: {program_name} {{command}} [options]")?;
@@ -14,6 +19,7 @@ fn help(program_name: &str, f: &mut dyn Write) -> std::io::Result<()> {
 fn main() {
     let stdout = &mut std::io::stdout();
     let stderr = &mut std::io::stderr();
+    let colored = stderr.is_terminal() && std::env::var_os("NO_COLOR").is_none();
 
     let mut args = std::env::args();
     let program_name = args
@@ -43,7 +49,7 @@ fn main() {
             for token in lexer {
                 match token {
                     Ok(token) => writeln!(stdout, "{}: {token}", token.get_location()).unwrap(),
-                    Err(e) => writeln!(stdout, "{e}").unwrap(),
+                    Err(e) => writeln!(stdout, "{}: {e}", e.get_location()).unwrap(),
                 }
             }
         }
@@ -58,18 +64,21 @@ fn main() {
                 writeln!(stderr, "Unable to open '{filepath}': {e}").unwrap();
                 std::process::exit(1)
             });
-            let expressions = parse_file(&filepath, &source).unwrap_or_else(|e| {
-                writeln!(stderr, "{e}").unwrap();
-                std::process::exit(1)
-            });
+            let (expressions, errors) = parse_file(&filepath, &source);
             for expression in expressions {
                 writeln!(stdout, "{expression:#?}").unwrap();
             }
+            if !errors.is_empty() {
+                for error in errors {
+                    report(error, &filepath, &source, colored, stderr);
+                }
+                std::process::exit(1)
+            }
         }
 
         "dump_ir" => {
             let filepath = args.next().unwrap_or_else(|| {
-                writeln!(stderr, "Expected a source file to parse the ast from").unwrap();
+                writeln!(stderr, "Expected a source file to bind the ir from").unwrap();
                 help(&program_name, stderr).unwrap();
                 std::process::exit(1)
             });
@@ -77,131 +86,354 @@ fn main() {
                 writeln!(stderr, "Unable to open '{filepath}': {e}").unwrap();
                 std::process::exit(1)
             });
-            let expressions = parse_file(&filepath, &source).unwrap_or_else(|e| {
-                writeln!(stderr, "{e}").unwrap();
+            let program = bind_program(&filepath, &source, colored, stderr);
+            for (id, typ) in program.types.iter() {
+                writeln!(stdout, "{id:?}: {}", typ.pretty_print(&program.types)).unwrap();
+            }
+            for (id, node) in program.nodes.iter() {
+                writeln!(stdout, "{id:?}: {node:#?}").unwrap();
+            }
+            writeln!(stdout, "main: {:?}", program.main_procedure).unwrap();
+        }
+
+        "run" => {
+            let filepath = args.next().unwrap_or_else(|| {
+                writeln!(stderr, "Expected a source file to run").unwrap();
+                help(&program_name, stderr).unwrap();
+                std::process::exit(1)
+            });
+            let source = std::fs::read_to_string(&filepath).unwrap_or_else(|e| {
+                writeln!(stderr, "Unable to open '{filepath}': {e}").unwrap();
                 std::process::exit(1)
             });
+            let program = bind_program(&filepath, &source, colored, stderr);
 
-            let builtin_location = SourceLocation {
-                filepath: "builtin.nkl",
-                position: 0,
-                line: 1.try_into().unwrap(),
-                column: 1.try_into().unwrap(),
+            let mut interpreter =
+                Interpreter::new(&program.nodes, &program.types, &program.common_types);
+
+            let Value::Procedure {
+                procedure: Some(main_procedure),
+            } = interpreter
+                .eval(program.main_procedure)
+                .unwrap_or_else(|e| {
+                    report(e, &filepath, &source, colored, stderr);
+                    std::process::exit(1)
+                })
+            else {
+                unreachable!()
             };
 
-            let mut nodes = Nodes::new();
-            let mut types = Nodes::new();
+            // the arguments are null terminated so they can be passed around as a `[^]u8`
+            let arguments = std::iter::once(filepath.clone())
+                .chain(args)
+                .map(|argument| {
+                    let bytes = argument
+                        .bytes()
+                        .chain(std::iter::once(0))
+                        .map(|byte| Value::Integer { value: byte.into() })
+                        .collect();
+                    Value::Pointer {
+                        address: Some(interpreter.allocate(bytes)),
+                    }
+                })
+                .collect::<Vec<_>>();
+            let length = arguments.len();
+            let arguments = Value::Slice {
+                data: Some(interpreter.allocate(arguments)),
+                length,
+            };
 
-            let typ = types.insert(Type::Type);
-            let type_node = nodes.insert(BoundNode::Type {
-                location: builtin_location,
-                end_location: builtin_location,
-                typ,
-                type_type: typ,
-            });
+            let Value::Integer { value: exit_code } = interpreter
+                .call(main_procedure, vec![arguments])
+                .unwrap_or_else(|e| {
+                    report(e, &filepath, &source, colored, stderr);
+                    std::process::exit(1)
+                })
+            else {
+                unreachable!()
+            };
+            std::process::exit(exit_code as i32)
+        }
 
-            let void = types.insert(Type::Void);
-            let void_node = nodes.insert(BoundNode::Type {
-                location: builtin_location,
-                end_location: builtin_location,
-                typ: void,
-                type_type: typ,
-            });
+        _ => {
+            writeln!(stderr, "Unknown command: '{command}'").unwrap();
+            help(&program_name, stderr).unwrap();
+            std::process::exit(1)
+        }
+    }
+}
 
-            let u8 = types.insert(Type::U8);
-            let u8_node = nodes.insert(BoundNode::Type {
-                location: builtin_location,
-                end_location: builtin_location,
-                typ: u8,
-                type_type: typ,
-            });
+fn report<'filepath>(
+    error: impl Into<Diagnostic<'filepath>>,
+    filepath: &str,
+    source: &str,
+    colored: bool,
+    stderr: &mut dyn Write,
+) {
+    error
+        .into()
+        .render(filepath, source, colored, stderr)
+        .unwrap();
+}
 
-            let int = types.insert(Type::Int);
-            let int_node = nodes.insert(BoundNode::Type {
-                location: builtin_location,
-                end_location: builtin_location,
-                typ: int,
-                type_type: typ,
-            });
+struct Program<'filepath> {
+    nodes: Nodes<BoundNode<'filepath>>,
+    types: Nodes<Type>,
+    common_types: CommonTypes<'filepath>,
+    main_procedure: NodeID<BoundNode<'filepath>>,
+}
 
-            let uint = types.insert(Type::UInt);
-            let uint_node = nodes.insert(BoundNode::Type {
-                location: builtin_location,
-                end_location: builtin_location,
-                typ: uint,
-                type_type: typ,
-            });
+fn bind_program<'filepath>(
+    filepath: &'filepath str,
+    source: &str,
+    colored: bool,
+    stderr: &mut dyn Write,
+) -> Program<'filepath> {
+    let (expressions, parsing_errors) = parse_file(filepath, source);
+    let has_parsing_errors = !parsing_errors.is_empty();
+    for error in parsing_errors {
+        report(error, filepath, source, colored, stderr);
+    }
 
-            let mut names = HashMap::from([
-                ("type", type_node),
-                ("void", void_node),
-                ("u8", u8_node),
-                ("int", int_node),
-                ("uint", uint_node),
-            ]);
-
-            let mut common_types = CommonTypes {
-                typ,
-                void,
-                int,
-                uint,
-                u8,
-                slice_types: HashMap::new(),
-                pointer_types: HashMap::new(),
-                multipointer_types: HashMap::new(),
-                procedure_types: HashMap::new(),
-            };
+    let builtin_location = SourceLocation {
+        filepath: "builtin.nkl",
+        position: 0,
+        line: 1.try_into().unwrap(),
+        column: 1.try_into().unwrap(),
+    };
 
-            let root = bind_file(
-                &filepath,
-                &expressions,
-                &mut nodes,
-                &mut types,
-                &mut names,
-                &mut common_types,
-            )
-            .unwrap_or_else(|e| {
-                writeln!(stderr, "{e}").unwrap();
-                std::process::exit(1)
-            });
+    let mut nodes = Nodes::new();
+    let mut types = Nodes::new();
 
-            let multipointer_of_u8 = common_types.get_multipointer(&mut types, u8);
-            let slice_of_multipointer_of_u8 =
-                common_types.get_slice(&mut types, multipointer_of_u8);
-            let main_procedure_type =
-                common_types.get_procedure(&mut types, &[slice_of_multipointer_of_u8], int);
-
-            let main_procedure = if let Some(&procedure_id) = names.get(&"main") {
-                let procedure = &nodes[procedure_id];
-                let procedure_type = procedure.get_type(&nodes);
-                if main_procedure_type != procedure_type {
-                    writeln!(
-                        stderr,
-                        "Expected the main function to have the type {}, but got {}",
-                        types[main_procedure_type].pretty_print(&types),
-                        types[procedure_type].pretty_print(&types),
-                    )
-                    .unwrap();
-                    std::process::exit(1)
-                }
-                procedure_id
-            } else {
-                writeln!(stderr, "Expected a procedure called main").unwrap();
-                std::process::exit(1)
-            };
+    let typ = types.insert(Type::Type);
+    let type_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ,
+        type_type: typ,
+    });
 
-            _ = main_procedure;
-            _ = root;
-        }
+    let void = types.insert(Type::Void);
+    let void_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: void,
+        type_type: typ,
+    });
 
-        "run" => {
-            todo!()
-        }
+    let never = types.insert(Type::Never);
+    let never_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: never,
+        type_type: typ,
+    });
 
-        _ => {
-            writeln!(stderr, "Unknown command: '{command}'").unwrap();
-            help(&program_name, stderr).unwrap();
+    let label = types.insert(Type::Label);
+    let label_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: label,
+        type_type: typ,
+    });
+
+    let bool = types.insert(Type::Bool);
+    let bool_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: bool,
+        type_type: typ,
+    });
+    let true_node = nodes.insert(BoundNode::Constant {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: bool,
+        value: Value::Bool { value: true },
+    });
+    let false_node = nodes.insert(BoundNode::Constant {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: bool,
+        value: Value::Bool { value: false },
+    });
+
+    let mut sized_integer = |integer_type| {
+        let integer = types.insert(integer_type);
+        let integer_node = nodes.insert(BoundNode::Type {
+            location: builtin_location,
+            end_location: builtin_location,
+            typ: integer,
+            type_type: typ,
+        });
+        (integer, integer_node)
+    };
+    let (u8, u8_node) = sized_integer(Type::U8);
+    let (u16, u16_node) = sized_integer(Type::U16);
+    let (u32, u32_node) = sized_integer(Type::U32);
+    let (u64, u64_node) = sized_integer(Type::U64);
+    let (s8, s8_node) = sized_integer(Type::S8);
+    let (s16, s16_node) = sized_integer(Type::S16);
+    let (s32, s32_node) = sized_integer(Type::S32);
+    let (s64, s64_node) = sized_integer(Type::S64);
+
+    let int = types.insert(Type::Int);
+    let int_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: int,
+        type_type: typ,
+    });
+
+    let uint = types.insert(Type::UInt);
+    let uint_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: uint,
+        type_type: typ,
+    });
+
+    let rawptr = types.insert(Type::RawPtr);
+    let rawptr_node = nodes.insert(BoundNode::Type {
+        location: builtin_location,
+        end_location: builtin_location,
+        typ: rawptr,
+        type_type: typ,
+    });
+
+    let generic_procedure = types.insert(Type::GenericProcedure);
+
+    let builtin = types.insert(Type::Builtin);
+    let mut builtin_node = |builtin_procedure| {
+        nodes.insert(BoundNode::Constant {
+            location: builtin_location,
+            end_location: builtin_location,
+            typ: builtin,
+            value: Value::Builtin {
+                builtin: builtin_procedure,
+            },
+        })
+    };
+    let append_node = builtin_node(Builtin::Append);
+    let reserve_node = builtin_node(Builtin::Reserve);
+    let resize_node = builtin_node(Builtin::Resize);
+    let pop_node = builtin_node(Builtin::Pop);
+    let free_node = builtin_node(Builtin::Free);
+
+    let mut names = HashMap::from([
+        ("type", type_node),
+        ("void", void_node),
+        ("never", never_node),
+        ("label", label_node),
+        ("bool", bool_node),
+        ("true", true_node),
+        ("false", false_node),
+        ("u8", u8_node),
+        ("u16", u16_node),
+        ("u32", u32_node),
+        ("u64", u64_node),
+        ("s8", s8_node),
+        ("s16", s16_node),
+        ("s32", s32_node),
+        ("s64", s64_node),
+        ("int", int_node),
+        ("uint", uint_node),
+        ("rawptr", rawptr_node),
+        ("append", append_node),
+        ("reserve", reserve_node),
+        ("resize", resize_node),
+        ("pop", pop_node),
+        ("free", free_node),
+    ]);
+
+    let mut common_types = CommonTypes {
+        typ,
+        void,
+        never,
+        label,
+        bool,
+        int,
+        uint,
+        u8,
+        u16,
+        u32,
+        u64,
+        s8,
+        s16,
+        s32,
+        s64,
+        rawptr,
+        generic_procedure,
+        slice_types: HashMap::new(),
+        array_types: HashMap::new(),
+        dynamic_array_types: HashMap::new(),
+        pointer_types: HashMap::new(),
+        multipointer_types: HashMap::new(),
+        procedure_types: HashMap::new(),
+        struct_types: HashMap::new(),
+        struct_default_values: HashMap::new(),
+        union_types: HashMap::new(),
+    };
+
+    bind_file(
+        filepath,
+        &expressions,
+        &mut nodes,
+        &mut types,
+        &mut names,
+        &mut common_types,
+    )
+    .unwrap_or_else(|e| {
+        report(e, filepath, source, colored, stderr);
+        std::process::exit(1)
+    });
+    // the binder still runs on a file with parsing errors so that its errors are reported too
+    if has_parsing_errors {
+        std::process::exit(1)
+    }
+
+    let multipointer_of_u8 = common_types.get_multipointer(&mut types, u8);
+    let slice_of_multipointer_of_u8 = common_types.get_slice(&mut types, multipointer_of_u8);
+    let main_procedure_type =
+        common_types.get_procedure(&mut types, &[slice_of_multipointer_of_u8], int);
+
+    let main_procedure = if let Some(&procedure_id) = names.get(&"main") {
+        let procedure = &nodes[procedure_id];
+        let procedure_type = procedure.get_type(&nodes);
+        if main_procedure_type != procedure_type {
+            let diagnostic = Diagnostic::new(
+                format!(
+                    "Expected the main function to have the type {}, but got {}",
+                    types[main_procedure_type].pretty_print(&types),
+                    types[procedure_type].pretty_print(&types),
+                ),
+                procedure.get_location(),
+                procedure.get_end_location(),
+            );
+            report(diagnostic, filepath, source, colored, stderr);
             std::process::exit(1)
         }
+        procedure_id
+    } else {
+        let start_of_file = SourceLocation {
+            filepath,
+            position: 0,
+            line: 1.try_into().unwrap(),
+            column: 1.try_into().unwrap(),
+        };
+        let diagnostic = Diagnostic::new(
+            "Expected a procedure called main".to_string(),
+            start_of_file,
+            start_of_file,
+        )
+        .with_note("The program starts by calling `main :: (args: [][^]u8) -> int`".to_string());
+        report(diagnostic, filepath, source, colored, stderr);
+        std::process::exit(1)
+    };
+
+    Program {
+        nodes,
+        types,
+        common_types,
+        main_procedure,
     }
 }
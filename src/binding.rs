@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::RangeInclusive};
 
 use crate::{
-    eval::{eval_bound_node, Value},
+    eval::{eval_bound_node, EvalError, Value},
     nodes::{NodeID, Nodes},
     parsing::Ast,
     tokens::{GetLocation, SourceLocation, TokenKind},
@@ -22,17 +22,25 @@ pub fn bind_file<'filepath, 'source>(
     nodes: &mut Nodes<BoundNode<'filepath>>,
     types: &mut Nodes<Type>,
     names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
-    common_types: &mut CommonTypes,
+    common_types: &mut CommonTypes<'filepath>,
 ) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let context = &mut BindingContext::default();
+    let constants = declare_constants(expressions, nodes, names, context);
     let bound_expressions = expressions
         .iter()
-        .map(|expression| {
-            if !matches!(expression, Ast::Constant { .. }) {
-                return Err(BindingError::OnlyConstantsInGlobalScope(
-                    expression.get_location(),
-                ));
+        .enumerate()
+        .map(|(i, expression)| {
+            if !matches!(expression, Ast::Constant { .. } | Ast::Error { .. }) {
+                return Err(BindingError::OnlyConstantsInGlobalScope {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                });
+            }
+            if let Some(&constant) = constants.get(&i) {
+                bind_unbound_constant(constant, nodes, types, common_types, context)?;
+                return Ok(constant);
             }
-            bind_expression(expression, nodes, types, names, common_types, None)
+            bind_expression(expression, nodes, types, names, common_types, context, None)
         })
         .collect::<Result<_, _>>()?;
 
@@ -50,12 +58,47 @@ pub fn bind_file<'filepath, 'source>(
     }))
 }
 
+/// A procedure with compile time parameters, which is kept as an ast so that it can be bound
+/// with the compile time arguments as constants
+struct GenericProcedure<'filepath, 'source> {
+    /// The `Ast::Procedure`
+    procedure: Ast<'filepath, 'source>,
+    /// The names that were in scope where the procedure was written
+    names: HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    /// The bound procedure for each of the lists of compile time arguments that it has been called with
+    instances: HashMap<Vec<Value<'filepath>>, NodeID<BoundNode<'filepath>>>,
+}
+
+/// A constant that has been declared, but is only bound when it is first used or reached
+struct UnboundConstant<'filepath, 'source> {
+    /// The `Ast::Constant`
+    constant: Ast<'filepath, 'source>,
+    /// The names that are in scope for the value, which include the constants declared after it
+    names: HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+}
+
+/// What the binder keeps track of for the whole file, besides the nodes and the types
+#[derive(Default)]
+struct BindingContext<'filepath, 'source> {
+    /// HashMap from `BoundNode::UnboundConstant` to the constant that it is replaced with once it is bound
+    unbound_constants: HashMap<NodeID<BoundNode<'filepath>>, UnboundConstant<'filepath, 'source>>,
+    /// HashMap from `BoundNode::GenericProcedure` to its ast and instances
+    generic_procedures: HashMap<NodeID<BoundNode<'filepath>>, GenericProcedure<'filepath, 'source>>,
+    /// The names of the parameters of each `BoundNode::Procedure`, which are not part of the procedure type either
+    procedure_parameter_names: HashMap<NodeID<BoundNode<'filepath>>, Vec<&'source str>>,
+    /// Whether the expression that is being bound might not run, like a branch of a conditional whose condition
+    /// is not known at compile time, nothing in it is evaluated at compile time then, because that could fail
+    /// or never finish even though the program would never run it
+    might_not_run: bool,
+}
+
 fn bind_expression<'filepath, 'source>(
     expression: &Ast<'filepath, 'source>,
     nodes: &mut Nodes<BoundNode<'filepath>>,
     types: &mut Nodes<Type>,
     names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
-    common_types: &mut CommonTypes,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
     type_hint: Option<NodeID<Type>>,
 ) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
     Ok(match *expression {
@@ -63,11 +106,70 @@ fn bind_expression<'filepath, 'source>(
             ref expressions, ..
         } => {
             let names = &mut names.clone();
+            let constants = declare_constants(expressions, nodes, names, context);
+
+            // labels are declared before everything else in the block so that they can be jumped to from anywhere in it
+            let mut labels = HashMap::new();
+            for (i, expression) in expressions.iter().enumerate() {
+                let Ast::Declaration {
+                    ref name_token,
+                    typ: Some(ref typ),
+                    value: None,
+                    ..
+                } = *expression
+                else {
+                    continue;
+                };
+                let Ast::Name {
+                    name_token: ref type_name_token,
+                } = **typ
+                else {
+                    continue;
+                };
+                let TokenKind::Name(type_name) = type_name_token.kind else {
+                    unreachable!()
+                };
+                let Some(&type_node) = names.get(type_name) else {
+                    continue;
+                };
+                bind_unbound_constant(type_node, nodes, types, common_types, context)?;
+                if !matches!(nodes[type_node], BoundNode::Type { typ, .. } if matches!(types[typ], Type::Label))
+                {
+                    continue;
+                }
+
+                let label = nodes.insert(BoundNode::Label {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    result_type: common_types.void,
+                });
+                let TokenKind::Name(name) = name_token.kind else {
+                    unreachable!()
+                };
+                names.insert(name, label);
+                labels.insert(i, label);
+            }
 
             let bound_expressions = expressions
                 .iter()
-                .map(|expression| {
-                    bind_expression(expression, nodes, types, names, common_types, None)
+                .enumerate()
+                .map(|(i, expression)| {
+                    if let Some(&label) = labels.get(&i) {
+                        Ok(label)
+                    } else if let Some(&constant) = constants.get(&i) {
+                        bind_unbound_constant(constant, nodes, types, common_types, context)?;
+                        Ok(constant)
+                    } else {
+                        bind_expression(
+                            expression,
+                            nodes,
+                            types,
+                            names,
+                            common_types,
+                            context,
+                            None,
+                        )
+                    }
                 })
                 .collect::<Result<Vec<_>, _>>()?;
 
@@ -90,168 +192,630 @@ fn bind_expression<'filepath, 'source>(
                 result_type,
             })
         }
-        Ast::Constant {
-            ref name_token,
-            ref typ,
-            ref value,
-            ..
+        Ast::Constant { ref name_token, .. } => {
+            let constant = bind_constant(
+                expression,
+                None,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                type_hint,
+            )?;
+            let constant = nodes.insert(constant);
+
+            let TokenKind::Name(name) = name_token.kind else { unreachable!() };
+            names.insert(name, constant);
+
+            constant
+        }
+        Ast::Declaration { .. } => bind_declaration(
+            expression,
+            false,
+            nodes,
+            types,
+            names,
+            common_types,
+            context,
+        )?,
+        Ast::NamedArgument { .. } => {
+            return Err(BindingError::InvalidNamedArgument {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+            });
+        }
+        Ast::Name { ref name_token } => {
+            let TokenKind::Name(name) = name_token.kind else { unreachable!() };
+            let Some(&node) = names.get(name) else {
+                return Err(BindingError::UnknownName {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    name: name.to_string(),
+                });
+            };
+            // constants are bound when they are first used, so that they can be used before they are declared
+            bind_unbound_constant(node, nodes, types, common_types, context)?;
+            if let BoundNode::UnboundConstant { typ: None, .. } = nodes[node] {
+                return Err(BindingError::RecursiveConstant {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    name: name.to_string(),
+                });
+            }
+            if let BoundNode::Label { .. } = nodes[node] {
+                return Err(BindingError::LabelUsedAsValue {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    label_location: nodes[node].get_location(),
+                    label_end_location: nodes[node].get_end_location(),
+                });
+            }
+            nodes.insert(BoundNode::Name {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                referenced_node: node,
+            })
+        }
+        Ast::Integer { .. } => {
+            bind_integer(expression, false, nodes, types, common_types, type_hint)?
+        }
+        Ast::ParenthesisedExpression { ref expression, .. } => bind_expression(
+            expression,
+            nodes,
+            types,
+            &mut names.clone(),
+            common_types,
+            context,
+            type_hint,
+        )?,
+        Ast::Unary {
+            ref operator_token,
+            ref operand,
         } => {
-            let typ = typ
-                .as_ref()
-                .map(|typ| {
-                    let typ = bind_expression(
-                        typ,
+            let operator = match operator_token.kind {
+                TokenKind::Plus => UnaryOperator::Identity,
+                TokenKind::Minus => UnaryOperator::Negate,
+                TokenKind::ExclamationMark => UnaryOperator::LogicalNot,
+                _ => unreachable!(),
+            };
+
+            // a negated literal is checked with its sign, so that the lowest value of a signed type like `-128` can be written
+            let operand =
+                if let (UnaryOperator::Negate, Ast::Integer { .. }) = (operator, &**operand) {
+                    bind_integer(operand, true, nodes, types, common_types, type_hint)?
+                } else {
+                    bind_expression(
+                        operand,
                         nodes,
                         types,
-                        &mut names.clone(),
+                        names,
                         common_types,
-                        Some(common_types.typ),
-                    )?;
+                        context,
+                        type_hint,
+                    )?
+                };
+            let operand_type = nodes[operand].get_type(nodes);
 
-                    if !nodes[typ].is_constant(nodes) {
-                        todo!()
-                    }
+            let is_valid = match operator {
+                UnaryOperator::Identity => types[operand_type].integer_range().is_some(),
+                UnaryOperator::Negate => types[operand_type]
+                    .integer_range()
+                    .is_some_and(|range| *range.start() < 0),
+                UnaryOperator::LogicalNot => matches!(types[operand_type], Type::Bool),
+            };
+            if !is_valid {
+                return Err(BindingError::InvalidUnaryOperator {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    operator,
+                    operand_type: types[operand_type].pretty_print(types),
+                });
+            }
 
-                    if !matches!(types[nodes[typ].get_type(nodes)], Type::Type) {
-                        todo!()
-                    }
+            let unary = nodes.insert(BoundNode::Unary {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                operator,
+                operand,
+                result_type: operand_type,
+            });
+            fold_constant(unary, nodes, types, common_types, context)?
+        }
+        Ast::AddressOf { ref operand, .. } => {
+            let type_hint = type_hint.and_then(|typ| match types[typ] {
+                Type::Pointer { pointed_to } => Some(pointed_to),
+                _ => None,
+            });
+            let operand = bind_expression(
+                operand,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                type_hint,
+            )?;
+            if !is_place(operand, nodes, types) {
+                return Err(BindingError::ExpectedPlace {
+                    location: nodes[operand].get_location(),
+                    end_location: nodes[operand].get_end_location(),
+                });
+            }
+            let result_type = common_types.get_pointer(types, nodes[operand].get_type(nodes));
+            nodes.insert(BoundNode::AddressOf {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                operand,
+                result_type,
+            })
+        }
+        Ast::Dereference { ref operand, .. } => {
+            let operand =
+                bind_expression(operand, nodes, types, names, common_types, context, None)?;
+            let operand_type = nodes[operand].get_type(nodes);
+            let Type::Pointer { pointed_to } = types[operand_type] else {
+                return Err(BindingError::NotAPointer {
+                    location: nodes[operand].get_location(),
+                    end_location: nodes[operand].get_end_location(),
+                    typ: types[operand_type].pretty_print(types),
+                    is_rawptr: matches!(types[operand_type], Type::RawPtr),
+                });
+            };
+            nodes.insert(BoundNode::Dereference {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                operand,
+                result_type: pointed_to,
+            })
+        }
+        Ast::Binary {
+            ref left,
+            ref operator_token,
+            ref right,
+        } => {
+            if operator_token.kind == TokenKind::Pipe {
+                let left = bind_type(left, nodes, types, names, common_types, context)?;
+                let right = bind_type(right, nodes, types, names, common_types, context)?;
+                let typ = common_types.get_union(types, &[left, right]);
+                return Ok(nodes.insert(BoundNode::Type {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    typ,
+                    type_type: common_types.typ,
+                }));
+            }
 
-                    let Value::Type { typ } = eval_bound_node(typ, nodes) else { unreachable!() };
-                    Ok(typ)
-                })
-                .transpose()?;
+            let operator = match operator_token.kind {
+                TokenKind::Plus => BinaryOperator::Add,
+                TokenKind::Minus => BinaryOperator::Subtract,
+                TokenKind::Asterisk => BinaryOperator::Multiply,
+                TokenKind::Slash => BinaryOperator::Divide,
+                TokenKind::Percent => BinaryOperator::Remainder,
+                TokenKind::EqualEqual => BinaryOperator::Equal,
+                TokenKind::ExclamationMarkEqual => BinaryOperator::NotEqual,
+                TokenKind::LessThan => BinaryOperator::LessThan,
+                TokenKind::LessThanEqual => BinaryOperator::LessThanEqual,
+                TokenKind::GreaterThan => BinaryOperator::GreaterThan,
+                TokenKind::GreaterThanEqual => BinaryOperator::GreaterThanEqual,
+                TokenKind::AmpersandAmpersand => BinaryOperator::LogicalAnd,
+                TokenKind::PipePipe => BinaryOperator::LogicalOr,
+                _ => unreachable!(),
+            };
 
-            let value = bind_expression(
-                value,
+            let operand_type_hint = match operator {
+                BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Remainder => type_hint,
+                BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => Some(common_types.bool),
+                _ => None,
+            };
+
+            let mut left_node = bind_expression(
+                left,
                 nodes,
                 types,
-                &mut names.clone(),
+                names,
+                common_types,
+                context,
+                operand_type_hint,
+            )?;
+            let mut left_type = nodes[left_node].get_type(nodes);
+            // the right side of `&&` and `||` only runs if the left side does not decide the result
+            let right_might_not_run = match operator {
+                BinaryOperator::LogicalAnd => constant_bool(left_node, nodes) != Some(true),
+                BinaryOperator::LogicalOr => constant_bool(left_node, nodes) != Some(false),
+                _ => false,
+            };
+            let right = bind_conditional_expression(
+                right,
+                right_might_not_run,
+                nodes,
+                types,
+                names,
                 common_types,
-                typ.or(type_hint),
+                context,
+                Some(left_type),
             )?;
+            let right_type = nodes[right].get_type(nodes);
 
-            if !nodes[value].is_constant(nodes) {
-                todo!()
+            // constants like integer literals get their type from the type hint,
+            // so the left side gets a chance to take on the type of the right side
+            if left_type != right_type && nodes[left_node].is_constant(nodes) {
+                left_node = bind_expression(
+                    left,
+                    nodes,
+                    types,
+                    names,
+                    common_types,
+                    context,
+                    Some(right_type),
+                )?;
+                left_type = nodes[left_node].get_type(nodes);
             }
 
-            let value_type = nodes[value].get_type(nodes);
-
-            if let Some(typ) = typ {
-                if value_type != typ {
-                    todo!()
+            let is_integer = types[left_type].integer_range().is_some();
+            let result_type = match operator {
+                // offsets a multipointer by a number of elements
+                BinaryOperator::Add | BinaryOperator::Subtract
+                    if matches!(types[left_type], Type::Multipointer { .. }) =>
+                {
+                    types[right_type]
+                        .integer_range()
+                        .is_some()
+                        .then_some(left_type)
+                }
+                BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Remainder => {
+                    (left_type == right_type && is_integer).then_some(left_type)
                 }
+                BinaryOperator::LessThan
+                | BinaryOperator::LessThanEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanEqual => {
+                    (left_type == right_type && is_integer).then_some(common_types.bool)
+                }
+                BinaryOperator::Equal | BinaryOperator::NotEqual => {
+                    (left_type == right_type).then_some(common_types.bool)
+                }
+                BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => (left_type == right_type
+                    && matches!(types[left_type], Type::Bool))
+                .then_some(common_types.bool),
+            };
+            let Some(result_type) = result_type else {
+                return Err(BindingError::InvalidBinaryOperator {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    operator,
+                    left_type: types[left_type].pretty_print(types),
+                    right_type: types[right_type].pretty_print(types),
+                });
+            };
+
+            let binary = nodes.insert(BoundNode::Binary {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                left: left_node,
+                operator,
+                right,
+                result_type,
+            });
+            fold_constant(binary, nodes, types, common_types, context)?
+        }
+        Ast::Assignment {
+            ref place,
+            ref operator_token,
+            ref value,
+        } => {
+            let place = bind_expression(place, nodes, types, names, common_types, context, None)?;
+            if !is_place(place, nodes, types) {
+                return Err(BindingError::ExpectedPlace {
+                    location: nodes[place].get_location(),
+                    end_location: nodes[place].get_end_location(),
+                });
             }
+            let place_type = nodes[place].get_type(nodes);
+
+            let operator = match operator_token.kind {
+                TokenKind::Equal => None,
+                TokenKind::PlusEqual => Some(BinaryOperator::Add),
+                TokenKind::MinusEqual => Some(BinaryOperator::Subtract),
+                TokenKind::AsteriskEqual => Some(BinaryOperator::Multiply),
+                TokenKind::SlashEqual => Some(BinaryOperator::Divide),
+                _ => unreachable!(),
+            };
 
-            let typ = typ.unwrap_or(value_type);
+            // `pointer += offset` offsets a multipointer by a number of elements
+            let is_offset = matches!(
+                operator,
+                Some(BinaryOperator::Add | BinaryOperator::Subtract)
+            ) && matches!(types[place_type], Type::Multipointer { .. });
 
-            let value = eval_bound_node(value, nodes);
+            let value = bind_expression(
+                value,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                (!is_offset).then_some(place_type),
+            )?;
+            let value_type = nodes[value].get_type(nodes);
+            // only a compound assignment can be invalid for a place type that the value converts to
+            let is_valid = if is_offset {
+                types[value_type].integer_range().is_some()
+            } else {
+                operator.is_none() || types[place_type].integer_range().is_some()
+            };
+            if !is_valid {
+                return Err(BindingError::InvalidBinaryOperator {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    operator: operator.unwrap(),
+                    left_type: types[place_type].pretty_print(types),
+                    right_type: types[value_type].pretty_print(types),
+                });
+            }
+            let value = if is_offset {
+                value
+            } else {
+                expect_type(value, place_type, nodes, types)?
+            };
 
-            let constant = nodes.insert(BoundNode::Constant {
+            nodes.insert(BoundNode::Assignment {
                 location: expression.get_location(),
                 end_location: expression.get_end_location(),
-                typ,
+                place,
+                operator,
                 value,
-            });
-
-            let TokenKind::Name(name) = name_token.kind else { unreachable!() };
-            names.insert(name, constant);
-
-            constant
+                result_type: common_types.void,
+            })
         }
-        Ast::Declaration {
-            ref name_token,
-            ref typ,
-            ref value,
+        Ast::If {
+            ref condition,
+            ref then_expression,
+            ref else_expression,
             ..
         } => {
-            let typ = typ
-                .as_ref()
-                .map(|typ| {
-                    let typ = bind_expression(
-                        typ,
+            let condition = bind_expression(
+                condition,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                Some(common_types.bool),
+            )?;
+            let Some(condition) = implicit_conversion(condition, common_types.bool, nodes, types)
+            else {
+                return Err(BindingError::TypeMismatch {
+                    location: nodes[condition].get_location(),
+                    end_location: nodes[condition].get_end_location(),
+                    expected: types[common_types.bool].pretty_print(types),
+                    got: types[nodes[condition].get_type(nodes)].pretty_print(types),
+                    is_rawptr: matches!(types[nodes[condition].get_type(nodes)], Type::RawPtr),
+                });
+            };
+            // a branch that a constant condition does not take never runs
+            let then_might_not_run = constant_bool(condition, nodes) != Some(true);
+            let else_might_not_run = constant_bool(condition, nodes) != Some(false);
+
+            let (then_expression, else_expression, result_type) =
+                if let Some(else_expression) = else_expression {
+                    let mut then_node = bind_conditional_expression(
+                        then_expression,
+                        then_might_not_run,
+                        nodes,
+                        types,
+                        &mut names.clone(),
+                        common_types,
+                        context,
+                        type_hint,
+                    )?;
+                    let mut then_type = nodes[then_node].get_type(nodes);
+                    let else_expression = bind_conditional_expression(
+                        else_expression,
+                        else_might_not_run,
                         nodes,
                         types,
                         &mut names.clone(),
                         common_types,
-                        Some(common_types.typ),
+                        context,
+                        Some(then_type),
                     )?;
+                    let else_type = nodes[else_expression].get_type(nodes);
 
-                    if !nodes[typ].is_constant(nodes) {
-                        todo!()
+                    // same as for binary operators, constants get a chance to take on the type of the other branch
+                    if then_type != else_type
+                        && !matches!(types[else_type], Type::Never)
+                        && nodes[then_node].is_constant(nodes)
+                    {
+                        then_node = bind_conditional_expression(
+                            then_expression,
+                            then_might_not_run,
+                            nodes,
+                            types,
+                            &mut names.clone(),
+                            common_types,
+                            context,
+                            Some(else_type),
+                        )?;
+                        then_type = nodes[then_node].get_type(nodes);
                     }
 
-                    if !matches!(types[nodes[typ].get_type(nodes)], Type::Type) {
-                        todo!()
+                    if let Some(else_expression) =
+                        implicit_conversion(else_expression, then_type, nodes, types)
+                    {
+                        (then_node, Some(else_expression), then_type)
+                    } else if let Some(then_node) =
+                        implicit_conversion(then_node, else_type, nodes, types)
+                    {
+                        (then_node, Some(else_expression), else_type)
+                    } else {
+                        return Err(BindingError::MismatchedBranchTypes {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                            then_location: nodes[then_node].get_location(),
+                            then_end_location: nodes[then_node].get_end_location(),
+                            else_location: nodes[else_expression].get_location(),
+                            else_end_location: nodes[else_expression].get_end_location(),
+                            then_type: types[then_type].pretty_print(types),
+                            else_type: types[else_type].pretty_print(types),
+                        });
                     }
-
-                    let Value::Type { typ } = eval_bound_node(typ, nodes) else { unreachable!() };
-                    Ok(typ)
-                })
-                .transpose()?;
-
-            let value = value
-                .as_ref()
-                .map(|value| {
-                    let value = bind_expression(
-                        value,
+                } else {
+                    let then_expression = bind_conditional_expression(
+                        then_expression,
+                        then_might_not_run,
                         nodes,
                         types,
                         &mut names.clone(),
                         common_types,
-                        typ,
+                        context,
+                        None,
                     )?;
-                    Ok(value)
-                })
-                .transpose()?;
-
-            let typ = typ.unwrap_or_else(|| nodes[value.unwrap()].get_type(nodes));
+                    (then_expression, None, common_types.void)
+                };
 
-            let declaration = nodes.insert(BoundNode::Declaration {
+            let if_node = nodes.insert(BoundNode::If {
                 location: expression.get_location(),
                 end_location: expression.get_end_location(),
-                typ,
-                value,
+                condition,
+                then_expression,
+                else_expression,
+                result_type,
             });
+            fold_constant(if_node, nodes, types, common_types, context)?
+        }
+        Ast::While {
+            ref condition,
+            ref body,
+            ..
+        } => {
+            let condition = bind_expression(
+                condition,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                Some(common_types.bool),
+            )?;
+            let Some(condition) = implicit_conversion(condition, common_types.bool, nodes, types)
+            else {
+                return Err(BindingError::TypeMismatch {
+                    location: nodes[condition].get_location(),
+                    end_location: nodes[condition].get_end_location(),
+                    expected: types[common_types.bool].pretty_print(types),
+                    got: types[nodes[condition].get_type(nodes)].pretty_print(types),
+                    is_rawptr: matches!(types[nodes[condition].get_type(nodes)], Type::RawPtr),
+                });
+            };
 
-            let TokenKind::Name(name) = name_token.kind else { unreachable!() };
-            names.insert(name, declaration);
+            let body = bind_conditional_expression(
+                body,
+                true,
+                nodes,
+                types,
+                &mut names.clone(),
+                common_types,
+                context,
+                None,
+            )?;
 
-            declaration
+            nodes.insert(BoundNode::While {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                condition,
+                body,
+                result_type: common_types.void,
+            })
         }
-        Ast::Name { ref name_token } => {
-            let TokenKind::Name(name) = name_token.kind else { unreachable!() };
-            let Some(&node) = names.get(name) else {
-                todo!()
+        Ast::Goto { ref name_token, .. } => {
+            let TokenKind::Name(name) = name_token.kind else {
+                unreachable!()
             };
-            nodes.insert(BoundNode::Name {
+            let Some(&label) = names
+                .get(name)
+                .filter(|&&node| matches!(nodes[node], BoundNode::Label { .. }))
+            else {
+                return Err(BindingError::UnknownLabel {
+                    location: name_token.get_location(),
+                    end_location: name_token.get_end_location(),
+                    name: name.to_string(),
+                });
+            };
+            nodes.insert(BoundNode::Goto {
                 location: expression.get_location(),
                 end_location: expression.get_end_location(),
-                referenced_node: node,
+                label,
+                result_type: common_types.never,
             })
         }
-        Ast::ParenthesisedExpression { ref expression, .. } => bind_expression(
-            expression,
-            nodes,
-            types,
-            &mut names.clone(),
-            common_types,
-            type_hint,
-        )?,
+        Ast::Error {
+            location,
+            end_location,
+        } => nodes.insert(BoundNode::Error {
+            location,
+            end_location,
+            result_type: common_types.never,
+        }),
         Ast::MemberAccess {
             ref operand,
             ref member_name_token,
             ..
         } => {
-            let operand = bind_expression(operand, nodes, types, names, common_types, None)?;
+            let operand =
+                bind_expression(operand, nodes, types, names, common_types, context, None)?;
             let operand_type = nodes[operand].get_type(nodes);
-            let TokenKind::Name(name) = member_name_token.kind else { unreachable!() };
-            let (member_index, result_type) = match types[operand_type] {
-                Type::Slice { inner_type } => match name {
-                    "data" => (0, common_types.get_pointer(types, inner_type)),
-                    "length" => (1, common_types.uint),
-                    _ => todo!(),
+
+            // the length of an array is part of its type, so it is known at compile time
+            if let (&Type::Array { length, .. }, &TokenKind::Name("length")) =
+                (&types[operand_type], &member_name_token.kind)
+            {
+                return Ok(nodes.insert(BoundNode::Constant {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    typ: common_types.uint,
+                    value: Value::Integer {
+                        value: length as i128,
+                    },
+                }));
+            }
+
+            let member = match (&types[operand_type], &member_name_token.kind) {
+                (&Type::Slice { inner_type }, &TokenKind::Name(name)) => match name {
+                    "data" => Some((0, common_types.get_pointer(types, inner_type))),
+                    "length" => Some((1, common_types.uint)),
+                    _ => None,
                 },
-                _ => todo!(),
+                (&Type::DynamicArray { inner_type }, &TokenKind::Name(name)) => match name {
+                    "data" => Some((0, common_types.get_pointer(types, inner_type))),
+                    "length" => Some((1, common_types.uint)),
+                    "capacity" => Some((2, common_types.uint)),
+                    _ => None,
+                },
+                (Type::Struct { members }, &TokenKind::Name(name)) => members
+                    .iter()
+                    .position(|member| member.name.as_deref() == Some(name))
+                    .map(|index| (index, members[index].typ)),
+                (typ, &TokenKind::Integer(index)) => usize::try_from(index)
+                    .ok()
+                    .and_then(|index| Some((index, typ.member_type(index)?))),
+                _ => None,
+            };
+            let Some((member_index, result_type)) = member else {
+                return Err(BindingError::UnknownMember {
+                    location: member_name_token.get_location(),
+                    end_location: member_name_token.get_end_location(),
+                    typ: types[operand_type].pretty_print(types),
+                    name: member_name_token.kind.to_string(),
+                });
             };
             nodes.insert(BoundNode::MemberAccess {
                 location: expression.get_location(),
@@ -261,132 +825,290 @@ fn bind_expression<'filepath, 'source>(
                 result_type,
             })
         }
-        Ast::Procedure {
-            ref parameters,
-            ref return_type,
-            ref body,
+        Ast::MemberIndex {
+            ref operand,
+            ref index,
             ..
         } => {
-            let names = &mut names.clone();
+            let operand =
+                bind_expression(operand, nodes, types, names, common_types, context, None)?;
+            let operand_type = nodes[operand].get_type(nodes);
 
-            let parameters = parameters
-                .iter()
-                .enumerate()
-                .map(|(i, parameter)| {
-                    let type_hint = type_hint.and_then(|typ| {
-                        if let Type::Procedure { ref parameters, .. } = types[typ] {
-                            parameters.get(i).copied()
-                        } else {
-                            None
-                        }
+            if let Type::Union { ref variants } = types[operand_type] {
+                let variants = variants.clone();
+                let variant = bind_type(index, nodes, types, names, common_types, context)?;
+                // a union can also be narrowed to a union of some of its variants
+                let is_variant = match types[variant] {
+                    Type::Union {
+                        variants: ref extracted_variants,
+                    } => extracted_variants
+                        .iter()
+                        .all(|variant| variants.contains(variant)),
+                    _ => variants.contains(&variant),
+                };
+                if !is_variant {
+                    return Err(BindingError::NotAUnionVariant {
+                        location: index.get_location(),
+                        end_location: index.get_end_location(),
+                        union_type: types[operand_type].pretty_print(types),
+                        variant: types[variant].pretty_print(types),
                     });
+                }
+                return Ok(nodes.insert(BoundNode::UnionExtract {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    operand,
+                    result_type: variant,
+                }));
+            }
 
-                    let parameter =
-                        bind_expression(parameter, nodes, types, names, common_types, type_hint)?;
-
-                    if let BoundNode::Declaration { value, .. } = nodes[parameter] {
-                        if value.is_some() {
-                            todo!() // cannot have default procedure parameter values (for now)
-                        }
-                    } else {
-                        unreachable!()
-                    }
-
-                    Ok(parameter)
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-
-            let return_type = bind_expression(
-                return_type,
+            bind_index(
+                expression,
+                operand,
+                index,
                 nodes,
                 types,
-                &mut names.clone(),
+                names,
                 common_types,
-                Some(common_types.typ),
-            )?;
-
-            if !nodes[return_type].is_constant(nodes) {
-                todo!()
-            }
-
-            if !matches!(types[nodes[return_type].get_type(nodes)], Type::Type) {
-                todo!()
-            }
-
-            let Value::Type { typ: return_type } = eval_bound_node(return_type, nodes) else { unreachable!() };
-            let body = bind_expression(body, nodes, types, names, common_types, Some(return_type))?;
-
-            if nodes[body].get_type(nodes) != return_type {
-                todo!()
-            }
-
-            let parameter_types = parameters
-                .iter()
-                .map(|&parameter| nodes[parameter].get_type(nodes))
-                .collect::<Vec<_>>();
-
-            let typ = type_hint
-                .and_then(|typ| {
-                    if let Type::Procedure {
-                        parameters: ref other_parameters,
-                        return_type: other_return_type,
-                    } = types[typ]
-                    {
-                        (other_return_type == return_type && other_parameters == &parameter_types)
-                            .then_some(typ)
-                    } else {
-                        None
+                context,
+            )?
+        }
+        Ast::Index {
+            ref operand,
+            ref index,
+            ..
+        } => {
+            let operand =
+                bind_expression(operand, nodes, types, names, common_types, context, None)?;
+            bind_index(
+                expression,
+                operand,
+                index,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+            )?
+        }
+        Ast::Slice {
+            ref operand,
+            ref low,
+            ref high,
+            ..
+        } => {
+            let operand =
+                bind_expression(operand, nodes, types, names, common_types, context, None)?;
+            let operand_type = nodes[operand].get_type(nodes);
+            let inner_type = match types[operand_type] {
+                Type::Array { inner_type, .. } => {
+                    // the slice points into the array, so the array has to be in memory
+                    if !is_place(operand, nodes, types) {
+                        return Err(BindingError::ExpectedPlace {
+                            location: nodes[operand].get_location(),
+                            end_location: nodes[operand].get_end_location(),
+                        });
                     }
-                })
-                .unwrap_or_else(|| {
-                    common_types.get_procedure(types, &parameter_types, return_type)
-                });
+                    inner_type
+                }
+                Type::Slice { inner_type } | Type::DynamicArray { inner_type } => inner_type,
+                Type::Multipointer { pointed_to } => {
+                    if high.is_none() {
+                        return Err(BindingError::ExpectedSliceEnd {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                        });
+                    }
+                    pointed_to
+                }
+                _ => {
+                    return Err(BindingError::NotSliceable {
+                        location: nodes[operand].get_location(),
+                        end_location: nodes[operand].get_end_location(),
+                        typ: types[operand_type].pretty_print(types),
+                    });
+                }
+            };
+
+            let [low, high] = [low, high].map(|bound| {
+                bound
+                    .as_ref()
+                    .map(|bound| {
+                        let bound = bind_expression(
+                            bound,
+                            nodes,
+                            types,
+                            names,
+                            common_types,
+                            context,
+                            None,
+                        )?;
+                        let bound_type = nodes[bound].get_type(nodes);
+                        if types[bound_type].integer_range().is_none() {
+                            return Err(BindingError::ExpectedIntegerIndex {
+                                location: nodes[bound].get_location(),
+                                end_location: nodes[bound].get_end_location(),
+                                got: types[bound_type].pretty_print(types),
+                            });
+                        }
+                        Ok(bound)
+                    })
+                    .transpose()
+            });
+            let (low, high) = (low?, high?);
 
-            nodes.insert(BoundNode::Procedure {
+            let result_type = common_types.get_slice(types, inner_type);
+            nodes.insert(BoundNode::Slice {
                 location: expression.get_location(),
                 end_location: expression.get_end_location(),
-                parameters,
-                return_type,
-                typ,
-                body,
+                operand,
+                low,
+                high,
+                result_type,
             })
         }
+        Ast::Procedure {
+            ref parameters,
+            ref return_type,
+            ref body,
+            ..
+        } => {
+            // the body can only be bound once the compile time arguments are known
+            if parameters.iter().any(is_compile_time_parameter) {
+                let generic = nodes.insert(BoundNode::GenericProcedure {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    typ: common_types.generic_procedure,
+                });
+                context.generic_procedures.insert(
+                    generic,
+                    GenericProcedure {
+                        procedure: expression.clone(),
+                        names: names.clone(),
+                        instances: HashMap::new(),
+                    },
+                );
+                generic
+            } else {
+                bind_procedure(
+                    expression,
+                    &parameters.iter().collect::<Vec<_>>(),
+                    return_type.as_deref(),
+                    body,
+                    nodes,
+                    types,
+                    names,
+                    common_types,
+                    context,
+                    type_hint,
+                )?
+            }
+        }
         Ast::ProcedureType {
             ref parameters,
             ref return_type,
             ..
         } => {
-            _ = parameters;
-            _ = return_type;
-            todo!()
+            // only the types of the parameters are part of the procedure type, the names are just for reading
+            let parameters = parameters
+                .iter()
+                .map(|parameter| {
+                    let Ast::Declaration {
+                        ref dollar_token,
+                        ref typ,
+                        ref value,
+                        ..
+                    } = *parameter
+                    else {
+                        return Err(BindingError::ExpectedParameterType {
+                            location: parameter.get_location(),
+                            end_location: parameter.get_end_location(),
+                        });
+                    };
+                    if dollar_token.is_some() {
+                        return Err(BindingError::InvalidCompileTimeParameter {
+                            location: parameter.get_location(),
+                            end_location: parameter.get_end_location(),
+                        });
+                    }
+                    if value.is_some() {
+                        return Err(BindingError::DefaultValueInProcedureType {
+                            location: parameter.get_location(),
+                            end_location: parameter.get_end_location(),
+                        });
+                    }
+                    let Some(typ) = typ else {
+                        return Err(BindingError::ExpectedParameterType {
+                            location: parameter.get_location(),
+                            end_location: parameter.get_end_location(),
+                        });
+                    };
+                    bind_type(typ, nodes, types, names, common_types, context)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let return_type = bind_type(return_type, nodes, types, names, common_types, context)?;
+            let typ = common_types.get_procedure(types, &parameters, return_type);
+
+            nodes.insert(BoundNode::Type {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                typ,
+                type_type: type_hint
+                    .and_then(|typ| matches!(types[typ], Type::Type).then_some(typ))
+                    .unwrap_or(common_types.typ),
+            })
         }
         Ast::Call {
             ref operand,
             ref arguments,
             ..
         } => {
-            let operand = bind_expression(operand, nodes, types, names, common_types, None)?;
+            let operand =
+                bind_expression(operand, nodes, types, names, common_types, context, None)?;
             let operand_type = nodes[operand].get_type(nodes);
             match types[operand_type] {
                 Type::Type => {
                     if !nodes[operand].is_constant(nodes) {
-                        todo!()
+                        return Err(expected_constant(operand, nodes));
                     }
-                    if !matches!(types[nodes[operand].get_type(nodes)], Type::Type) {
-                        todo!()
+                    let Value::Type { typ: to_type } = eval_bound_node(operand, nodes, types, common_types)? else { unreachable!() };
+
+                    if arguments
+                        .iter()
+                        .any(|argument| matches!(*argument, Ast::NamedArgument { .. }))
+                    {
+                        return bind_named_struct_arguments(
+                            expression,
+                            to_type,
+                            arguments,
+                            nodes,
+                            types,
+                            names,
+                            common_types,
+                            context,
+                        );
                     }
-                    let Value::Type { typ: to_type } = eval_bound_node(operand, nodes) else { unreachable!() };
 
                     let arguments = arguments
                         .iter()
                         .enumerate()
                         .map(|(i, argument)| {
-                            let type_hint = match (&types[to_type], i, arguments.len()) {
-                                (_, 0, 1) => Some(to_type),
-                                (_, _, _) => None,
-                            };
+                            let type_hint =
+                                match (types[to_type].member_count(), i, arguments.len()) {
+                                    (Some(_), _, _) => types[to_type].member_type(i),
+                                    (None, 0, 1) => Some(to_type),
+                                    (None, _, _) => None,
+                                };
 
-                            bind_expression(argument, nodes, types, names, common_types, type_hint)
+                            bind_expression(
+                                argument,
+                                nodes,
+                                types,
+                                names,
+                                common_types,
+                                context,
+                                type_hint,
+                            )
                         })
                         .collect::<Result<Vec<_>, _>>()?;
 
@@ -394,15 +1116,61 @@ fn bind_expression<'filepath, 'source>(
                         .iter()
                         .map(|&argument| nodes[argument].get_type(nodes))
                         .collect::<Vec<_>>();
+                    let default_values = trailing_default_values(
+                        expression,
+                        to_type,
+                        arguments.len(),
+                        nodes,
+                        types,
+                        common_types,
+                    )
+                    .unwrap_or_default();
 
                     if argument_types.len() == 1 && to_type == argument_types[0] {
                         arguments[0]
+                    } else if let Some(converted) = match arguments[..] {
+                        [argument] => implicit_conversion(argument, to_type, nodes, types),
+                        _ => None,
+                    } {
+                        converted
+                    } else if let Some(members) = convert_members(
+                        &[&arguments[..], &default_values[..]].concat(),
+                        to_type,
+                        nodes,
+                        types,
+                    ) {
+                        nodes.insert(BoundNode::Cast {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                            to_type,
+                            from_expressions: members,
+                        })
+                    } else if let Some(default_values) = common_types
+                        .struct_default_values
+                        .get(&to_type)
+                        .filter(|_| argument_types.is_empty())
+                    {
+                        // the default values would have been converted above if every member had one
+                        let Type::Struct { ref members } = types[to_type] else {
+                            unreachable!()
+                        };
+                        let missing = default_values.iter().position(Option::is_none).unwrap();
+                        return Err(BindingError::MissingArgument {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                            name: members[missing].name.clone().unwrap(),
+                        });
+                    } else if argument_types.is_empty()
+                        && !has_default_value(to_type, types, common_types)
+                    {
+                        return Err(BindingError::NoDefaultValue {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                            typ: types[to_type].pretty_print(types),
+                        });
                     } else if argument_types.is_empty()
                         || (argument_types.len() == 1
-                            && matches!(
-                                (&types[to_type], &types[argument_types[0]]),
-                                (Type::Int, Type::UInt)
-                            ))
+                            && is_explicit_conversion(argument_types[0], to_type, types))
                     {
                         nodes.insert(BoundNode::Cast {
                             location: expression.get_location(),
@@ -411,46 +1179,245 @@ fn bind_expression<'filepath, 'source>(
                             from_expressions: arguments,
                         })
                     } else {
-                        todo!()
+                        return Err(BindingError::InvalidCast {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                            from_types: argument_types
+                                .iter()
+                                .map(|&typ| types[typ].pretty_print(types))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            to_type: types[to_type].pretty_print(types),
+                        });
+                    }
+                }
+                Type::Procedure { ref parameters, .. } => {
+                    let parameter_count = parameters.len();
+                    let parameter_names = known_procedure(operand, nodes, types, common_types)?
+                        .map(|procedure| context.procedure_parameter_names[&procedure].clone());
+                    let arguments = order_arguments(
+                        expression,
+                        arguments,
+                        parameter_count,
+                        parameter_names.as_deref(),
+                    )?;
+                    bind_procedure_call(
+                        expression,
+                        operand,
+                        &arguments,
+                        nodes,
+                        types,
+                        names,
+                        common_types,
+                        context,
+                    )?
+                }
+                Type::GenericProcedure => {
+                    if !nodes[operand].is_constant(nodes) {
+                        return Err(expected_constant(operand, nodes));
                     }
+                    let Value::GenericProcedure { procedure } =
+                        eval_bound_node(operand, nodes, types, common_types)?
+                    else {
+                        unreachable!()
+                    };
+                    let (instance, arguments) = instantiate_generic_procedure(
+                        expression,
+                        procedure,
+                        arguments,
+                        nodes,
+                        types,
+                        names,
+                        common_types,
+                        context,
+                    )?;
+                    bind_procedure_call(
+                        expression,
+                        instance,
+                        &arguments,
+                        nodes,
+                        types,
+                        names,
+                        common_types,
+                        context,
+                    )?
                 }
-                Type::Procedure {
-                    ref parameters,
-                    return_type,
-                } => {
-                    _ = parameters;
-                    _ = return_type;
-                    todo!()
+                Type::Builtin => {
+                    if !nodes[operand].is_constant(nodes) {
+                        return Err(expected_constant(operand, nodes));
+                    }
+                    let Value::Builtin { builtin } =
+                        eval_bound_node(operand, nodes, types, common_types)?
+                    else {
+                        unreachable!()
+                    };
+                    bind_builtin_call(
+                        expression,
+                        builtin,
+                        operand,
+                        arguments,
+                        nodes,
+                        types,
+                        names,
+                        common_types,
+                        context,
+                    )?
                 }
                 _ => {
-                    todo!()
+                    return Err(BindingError::NotCallable {
+                        location: nodes[operand].get_location(),
+                        end_location: nodes[operand].get_end_location(),
+                        typ: types[operand_type].pretty_print(types),
+                    });
                 }
             }
         }
-        Ast::StructType { ref members, .. } => {
-            _ = members;
-            todo!();
+        Ast::StructValue { ref values, .. } => {
+            // a struct or an array type with the same number of members gives a type hint for each value,
+            // or a struct type with default values for the members after them
+            let default_values = type_hint.and_then(|typ| {
+                trailing_default_values(expression, typ, values.len(), nodes, types, common_types)
+            });
+            let member_types_hint = type_hint.filter(|&typ| {
+                types[typ].member_count() == Some(values.len()) || default_values.is_some()
+            });
+            let values = values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let type_hint = member_types_hint.and_then(|typ| types[typ].member_type(i));
+                    bind_expression(value, nodes, types, names, common_types, context, type_hint)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // the value takes on the struct or array type it is being converted to if it can,
+            // otherwise it gets a struct type without member names
+            let (to_type, from_expressions) = if let Some((to_type, members)) =
+                type_hint.and_then(|typ| {
+                    let values = [&values[..], &default_values.unwrap_or_default()[..]].concat();
+                    Some((typ, convert_members(&values, typ, nodes, types)?))
+                }) {
+                (to_type, members)
+            } else {
+                let members = values
+                    .iter()
+                    .map(|&value| StructMember {
+                        name: None,
+                        typ: nodes[value].get_type(nodes),
+                    })
+                    .collect::<Vec<_>>();
+                (common_types.get_struct(types, &members), values)
+            };
+
+            let struct_value = nodes.insert(BoundNode::Cast {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                to_type,
+                from_expressions,
+            });
+            fold_constant(struct_value, nodes, types, common_types, context)?
         }
-        Ast::SliceType { ref operand, .. } => {
-            let operand = bind_expression(
-                operand,
-                nodes,
-                types,
-                &mut names.clone(),
-                common_types,
-                Some(common_types.typ),
-            )?;
+        Ast::StructType { ref members, .. } => {
+            let mut declarations: HashMap<&str, &Ast<'filepath, 'source>> = HashMap::new();
+            let members = members
+                .iter()
+                .map(|member| {
+                    let Ast::Declaration {
+                        ref dollar_token,
+                        ref name_token,
+                        ref typ,
+                        ref value,
+                        ..
+                    } = *member
+                    else {
+                        unreachable!()
+                    };
+                    if dollar_token.is_some() {
+                        return Err(BindingError::InvalidCompileTimeParameter {
+                            location: member.get_location(),
+                            end_location: member.get_end_location(),
+                        });
+                    }
+                    let TokenKind::Name(name) = name_token.kind else {
+                        unreachable!()
+                    };
 
-            if !nodes[operand].is_constant(nodes) {
-                todo!()
-            }
+                    if let Some(previous) = declarations.insert(name, member) {
+                        return Err(BindingError::DuplicateMemberName {
+                            location: member.get_location(),
+                            end_location: member.get_end_location(),
+                            previous_location: previous.get_location(),
+                            previous_end_location: previous.get_end_location(),
+                            name: name.to_string(),
+                        });
+                    }
 
-            if !matches!(types[nodes[operand].get_type(nodes)], Type::Type) {
-                todo!()
-            }
+                    let typ = typ
+                        .as_ref()
+                        .map(|typ| bind_type(typ, nodes, types, names, common_types, context))
+                        .transpose()?;
+                    let default_value = value
+                        .as_ref()
+                        .map(|value| {
+                            let value = bind_expression(
+                                value,
+                                nodes,
+                                types,
+                                &mut names.clone(),
+                                common_types,
+                                context,
+                                typ,
+                            )?;
+                            let value = if let Some(typ) = typ {
+                                expect_type(value, typ, nodes, types)?
+                            } else {
+                                value
+                            };
+                            if !nodes[value].is_constant(nodes) {
+                                return Err(expected_constant(value, nodes));
+                            }
+                            Ok(value)
+                        })
+                        .transpose()?;
+
+                    let member = StructMember {
+                        name: Some(name.to_string()),
+                        typ: typ.unwrap_or_else(|| nodes[default_value.unwrap()].get_type(nodes)),
+                    };
+                    let default_value = default_value
+                        .map(|node| {
+                            let value = eval_bound_node(node, nodes, types, common_types)?;
+                            if value.has_address() {
+                                return Err(BindingError::ConstantHasAddress {
+                                    location: nodes[node].get_location(),
+                                    end_location: nodes[node].get_end_location(),
+                                });
+                            }
+                            Ok(value)
+                        })
+                        .transpose()?;
+                    Ok((member, default_value))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let (members, default_values): (Vec<_>, Vec<_>) = members.into_iter().unzip();
 
-            let Value::Type { typ: operand } = eval_bound_node(operand, nodes) else { unreachable!() };
+            let typ = if default_values.iter().any(Option::is_some) {
+                common_types.get_struct_with_default_values(types, &members, &default_values)
+            } else {
+                common_types.get_struct(types, &members)
+            };
 
+            nodes.insert(BoundNode::Type {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                typ,
+                type_type: type_hint
+                    .and_then(|typ| matches!(types[typ], Type::Type).then_some(typ))
+                    .unwrap_or(common_types.typ),
+            })
+        }
+        Ast::SliceType { ref operand, .. } => {
+            let operand = bind_type(operand, nodes, types, names, common_types, context)?;
             let typ = common_types.get_slice(types, operand);
 
             nodes.insert(BoundNode::Type {
@@ -467,30 +1434,65 @@ fn bind_expression<'filepath, 'source>(
             ref operand,
             ..
         } => {
-            _ = length;
-            _ = operand;
-            todo!()
-        }
-        Ast::MultipointerType { ref operand, .. } => {
-            let operand = bind_expression(
-                operand,
+            let length = bind_expression(
+                length,
                 nodes,
                 types,
-                &mut names.clone(),
+                names,
                 common_types,
-                Some(common_types.typ),
+                context,
+                Some(common_types.uint),
             )?;
-
-            if !nodes[operand].is_constant(nodes) {
-                todo!()
+            let length = expect_type(length, common_types.uint, nodes, types)?;
+            if !nodes[length].is_constant(nodes) {
+                return Err(expected_constant(length, nodes));
             }
+            let Value::Integer { value: length } =
+                eval_bound_node(length, nodes, types, common_types)?
+            else {
+                unreachable!()
+            };
 
-            if !matches!(types[nodes[operand].get_type(nodes)], Type::Type) {
-                todo!()
-            }
+            let operand = bind_type(operand, nodes, types, names, common_types, context)?;
+            let typ = common_types.get_array(types, length.try_into().unwrap(), operand);
+
+            nodes.insert(BoundNode::Type {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                typ,
+                type_type: type_hint
+                    .and_then(|typ| matches!(types[typ], Type::Type).then_some(typ))
+                    .unwrap_or(common_types.typ),
+            })
+        }
+        Ast::DynamicArrayType { ref operand, .. } => {
+            let operand = bind_type(operand, nodes, types, names, common_types, context)?;
+            let typ = common_types.get_dynamic_array(types, operand);
 
-            let Value::Type { typ: operand } = eval_bound_node(operand, nodes) else { unreachable!() };
+            nodes.insert(BoundNode::Type {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                typ,
+                type_type: type_hint
+                    .and_then(|typ| matches!(types[typ], Type::Type).then_some(typ))
+                    .unwrap_or(common_types.typ),
+            })
+        }
+        Ast::PointerType { ref operand, .. } => {
+            let operand = bind_type(operand, nodes, types, names, common_types, context)?;
+            let typ = common_types.get_pointer(types, operand);
 
+            nodes.insert(BoundNode::Type {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                typ,
+                type_type: type_hint
+                    .and_then(|typ| matches!(types[typ], Type::Type).then_some(typ))
+                    .unwrap_or(common_types.typ),
+            })
+        }
+        Ast::MultipointerType { ref operand, .. } => {
+            let operand = bind_type(operand, nodes, types, names, common_types, context)?;
             let typ = common_types.get_multipointer(types, operand);
 
             nodes.insert(BoundNode::Type {
@@ -505,8 +1507,1790 @@ fn bind_expression<'filepath, 'source>(
     })
 }
 
-#[derive(Debug, Display)]
-pub enum BindingError<'filepath> {
-    #[display(fmt = "{_0}: Only constants are allowed in the global scope")]
-    OnlyConstantsInGlobalScope(SourceLocation<'filepath>),
+/// Binds the value of an `Ast::Constant` and evaluates it, `unbound` is the `BoundNode::UnboundConstant`
+/// that the constant was declared with, which gets its type first if it is known without the value
+#[allow(clippy::too_many_arguments)]
+fn bind_constant<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    unbound: Option<NodeID<BoundNode<'filepath>>>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+    type_hint: Option<NodeID<Type>>,
+) -> Result<BoundNode<'filepath>, BindingError<'filepath>> {
+    let Ast::Constant {
+        ref typ, ref value, ..
+    } = *expression
+    else {
+        unreachable!()
+    };
+
+    let typ = typ
+        .as_ref()
+        .map(|typ| bind_type(typ, nodes, types, names, common_types, context))
+        .transpose()?;
+
+    // a procedure can call itself if its type is known before its body is bound
+    let procedure_type = match typ {
+        Some(typ) => Some(typ),
+        None => procedure_signature(value, nodes, types, names, common_types, context)?,
+    }
+    .filter(|&typ| matches!(types[typ], Type::Procedure { .. }));
+    if let Some((unbound, procedure_type)) = unbound.zip(procedure_type) {
+        nodes[unbound] = BoundNode::UnboundConstant {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            typ: Some(procedure_type),
+        };
+    }
+
+    let value = bind_expression(
+        value,
+        nodes,
+        types,
+        &mut names.clone(),
+        common_types,
+        context,
+        typ.or(procedure_type).or(type_hint),
+    )?;
+
+    if !nodes[value].is_constant(nodes) {
+        return Err(expected_constant(value, nodes));
+    }
+
+    let value_type = nodes[value].get_type(nodes);
+    let typ = if let Some(typ) = typ {
+        expect_type(value, typ, nodes, types)?;
+        typ
+    } else {
+        value_type
+    };
+
+    let value_location = nodes[value].get_location();
+    let value_end_location = nodes[value].get_end_location();
+    let value = eval_bound_node(value, nodes, types, common_types)?;
+    if value.has_address() {
+        return Err(BindingError::ConstantHasAddress {
+            location: value_location,
+            end_location: value_end_location,
+        });
+    }
+
+    Ok(BoundNode::Constant {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        typ,
+        value,
+    })
+}
+
+/// The type of a procedure value that has the types of its parameters and its return type written out,
+/// which can be bound without its body
+fn procedure_signature<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<Option<NodeID<Type>>, BindingError<'filepath>> {
+    let Ast::Procedure {
+        ref parameters,
+        return_type: Some(ref return_type),
+        ..
+    } = *expression
+    else {
+        return Ok(None);
+    };
+
+    let mut parameter_types = vec![];
+    for parameter in parameters {
+        let Ast::Declaration {
+            dollar_token: None,
+            typ: Some(ref typ),
+            ..
+        } = *parameter
+        else {
+            return Ok(None);
+        };
+        parameter_types.push(bind_type(typ, nodes, types, names, common_types, context)?);
+    }
+    let return_type = bind_type(return_type, nodes, types, names, common_types, context)?;
+    Ok(Some(common_types.get_procedure(
+        types,
+        &parameter_types,
+        return_type,
+    )))
+}
+
+/// Declares the constants in `expressions` before any of them are bound, so that they can be used anywhere
+/// in the block or file that they are in, and returns the `BoundNode::UnboundConstant` for the index of each
+fn declare_constants<'filepath, 'source>(
+    expressions: &[Ast<'filepath, 'source>],
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> HashMap<usize, NodeID<BoundNode<'filepath>>> {
+    let mut constants = HashMap::new();
+    for (i, expression) in expressions.iter().enumerate() {
+        let Ast::Constant { ref name_token, .. } = *expression else {
+            continue;
+        };
+        let constant = nodes.insert(BoundNode::UnboundConstant {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            typ: None,
+        });
+        let TokenKind::Name(name) = name_token.kind else {
+            unreachable!()
+        };
+        names.insert(name, constant);
+        constants.insert(i, constant);
+    }
+
+    for (&i, &constant) in &constants {
+        context.unbound_constants.insert(
+            constant,
+            UnboundConstant {
+                constant: expressions[i].clone(),
+                names: names.clone(),
+            },
+        );
+    }
+    constants
+}
+
+/// Binds a constant that was declared with `declare_constants` if it has not been bound yet,
+/// does nothing for any other node
+fn bind_unbound_constant<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, '_>,
+) -> Result<(), BindingError<'filepath>> {
+    let Some(UnboundConstant {
+        constant,
+        mut names,
+    }) = context.unbound_constants.remove(&node)
+    else {
+        return Ok(());
+    };
+    nodes[node] = bind_constant(
+        &constant,
+        Some(node),
+        nodes,
+        types,
+        &mut names,
+        common_types,
+        context,
+        None,
+    )?;
+    Ok(())
+}
+
+/// The value of `node` if it is a `bool` that is known at compile time
+fn constant_bool<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    nodes: &Nodes<BoundNode<'filepath>>,
+) -> Option<bool> {
+    match nodes[node] {
+        BoundNode::Constant {
+            value: Value::Bool { value },
+            ..
+        } => Some(value),
+        _ => None,
+    }
+}
+
+/// Binds an expression that does not always run when the expression around it does if `might_not_run`,
+/// so that it is not evaluated at compile time (see `BindingContext::might_not_run`)
+#[allow(clippy::too_many_arguments)]
+fn bind_conditional_expression<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    might_not_run: bool,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+    type_hint: Option<NodeID<Type>>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let outer_might_not_run = context.might_not_run;
+    context.might_not_run |= might_not_run;
+    let result = bind_expression(
+        expression,
+        nodes,
+        types,
+        names,
+        common_types,
+        context,
+        type_hint,
+    );
+    context.might_not_run = outer_might_not_run;
+    result
+}
+
+/// Binds a variable declaration, or the declaration of a parameter if `is_parameter`,
+/// which does not need a default value because it is always given an argument
+fn bind_declaration<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    is_parameter: bool,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let Ast::Declaration {
+        ref dollar_token,
+        ref name_token,
+        ref typ,
+        ref value,
+        ..
+    } = *expression
+    else {
+        unreachable!()
+    };
+
+    // the compile time parameters of procedures are bound when the procedure is called
+    if dollar_token.is_some() {
+        return Err(BindingError::InvalidCompileTimeParameter {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+        });
+    }
+
+    let typ = typ
+        .as_ref()
+        .map(|typ| bind_type(typ, nodes, types, names, common_types, context))
+        .transpose()?;
+
+    let value = value
+        .as_ref()
+        .map(|value| {
+            let value = bind_expression(
+                value,
+                nodes,
+                types,
+                &mut names.clone(),
+                common_types,
+                context,
+                typ,
+            )?;
+            if let Some(typ) = typ {
+                expect_type(value, typ, nodes, types)
+            } else {
+                Ok(value)
+            }
+        })
+        .transpose()?;
+
+    let typ = typ.unwrap_or_else(|| nodes[value.unwrap()].get_type(nodes));
+
+    if matches!(types[typ], Type::Label) {
+        return Err(BindingError::InvalidLabelDeclaration {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+        });
+    }
+    if !is_parameter && value.is_none() && !has_default_value(typ, types, common_types) {
+        return Err(BindingError::NoDefaultValue {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            typ: types[typ].pretty_print(types),
+        });
+    }
+
+    let declaration = nodes.insert(BoundNode::Declaration {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        typ,
+        value,
+    });
+
+    let TokenKind::Name(name) = name_token.kind else { unreachable!() };
+    names.insert(name, declaration);
+
+    Ok(declaration)
+}
+
+/// Binds an integer literal, which is checked to fit in its type as `-value` if it is `negated`
+fn bind_integer<'filepath>(
+    expression: &Ast<'filepath, '_>,
+    negated: bool,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'filepath>,
+    type_hint: Option<NodeID<Type>>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let Ast::Integer { ref integer_token } = *expression else {
+        unreachable!()
+    };
+    let TokenKind::Integer(value) = integer_token.kind else {
+        unreachable!()
+    };
+
+    // a union with a single integer variant gives the literal that type, like in `x: u8 | bool = 5`
+    let typ = type_hint
+        .and_then(|typ| match types[typ] {
+            Type::Union { ref variants } => {
+                let mut integer_variants = variants
+                    .iter()
+                    .filter(|&&variant| types[variant].integer_range().is_some());
+                match (integer_variants.next(), integer_variants.next()) {
+                    (Some(&variant), None) => Some(variant),
+                    _ => None,
+                }
+            }
+            _ => Some(typ),
+        })
+        .filter(|&typ| types[typ].integer_range().is_some())
+        .unwrap_or(common_types.int);
+
+    let signed_value = i128::try_from(value)
+        .ok()
+        .map(|value| if negated { -value } else { value });
+    if !signed_value.is_some_and(|value| types[typ].integer_range().unwrap().contains(&value)) {
+        return Err(BindingError::IntegerLiteralOutOfRange {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            value,
+            negated,
+            typ: types[typ].pretty_print(types),
+            range: types[typ].integer_range().unwrap(),
+        });
+    }
+
+    Ok(nodes.insert(BoundNode::Integer {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        typ,
+        value,
+    }))
+}
+
+/// Binds an expression that has to be a type known at compile time
+fn bind_type<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<NodeID<Type>, BindingError<'filepath>> {
+    let typ = bind_expression(
+        expression,
+        nodes,
+        types,
+        &mut names.clone(),
+        common_types,
+        context,
+        Some(common_types.typ),
+    )?;
+
+    let type_type = nodes[typ].get_type(nodes);
+    if !matches!(types[type_type], Type::Type) {
+        return Err(BindingError::ExpectedType {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            got: types[type_type].pretty_print(types),
+        });
+    }
+
+    if !nodes[typ].is_constant(nodes) {
+        return Err(expected_constant(typ, nodes));
+    }
+
+    let Value::Type { typ } = eval_bound_node(typ, nodes, types, common_types)? else {
+        unreachable!()
+    };
+    Ok(typ)
+}
+
+/// Binds a call to a procedure value with the arguments in the order of the parameters,
+/// the parameter types are the type hints of the arguments
+#[allow(clippy::too_many_arguments)]
+fn bind_procedure_call<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    operand: NodeID<BoundNode<'filepath>>,
+    arguments: &[Option<&Ast<'filepath, 'source>>],
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let Type::Procedure {
+        ref parameters,
+        return_type,
+    } = types[nodes[operand].get_type(nodes)]
+    else {
+        unreachable!()
+    };
+    let parameters = parameters.clone();
+    let procedure = known_procedure(operand, nodes, types, common_types)?;
+
+    let arguments = arguments
+        .iter()
+        .zip(parameters)
+        .enumerate()
+        .map(|(i, (argument, parameter_type))| {
+            let Some(argument) = argument else {
+                // only a procedure that is known at compile time has default values for the arguments that are left out
+                let Some(procedure) = procedure else {
+                    return Err(BindingError::WrongArgumentCount {
+                        location: expression.get_location(),
+                        end_location: expression.get_end_location(),
+                        expected: arguments.len(),
+                        got: arguments.iter().flatten().count(),
+                    });
+                };
+                let BoundNode::Procedure { ref parameters, .. } = nodes[procedure] else {
+                    unreachable!()
+                };
+                let BoundNode::Declaration { value, .. } = nodes[parameters[i]] else {
+                    unreachable!()
+                };
+                return value.ok_or_else(|| BindingError::MissingArgument {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    name: context.procedure_parameter_names[&procedure][i].to_string(),
+                });
+            };
+
+            let argument = bind_expression(
+                argument,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                Some(parameter_type),
+            )?;
+            expect_type(argument, parameter_type, nodes, types)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let call = nodes.insert(BoundNode::Call {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        operand,
+        arguments,
+        result_type: return_type,
+    });
+    // the error is in the body of the procedure, so it also points at the call that ran into it
+    fold_constant(call, nodes, types, common_types, context).map_err(|error| match error {
+        BindingError::EvalError(error) => BindingError::CompileTimeCallFailed {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            error,
+        },
+        error => error,
+    })
+}
+
+/// Binds a struct value with named arguments like `Point(x = 1, y = 2)`, whose arguments are put in the order
+/// of the members and have to be the member types, instead of being converted like the arguments of a cast
+#[allow(clippy::too_many_arguments)]
+fn bind_named_struct_arguments<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    typ: NodeID<Type>,
+    arguments: &[Ast<'filepath, 'source>],
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    // the struct values that were not given a struct type do not have member names
+    let member_names = match types[typ] {
+        Type::Struct { ref members } => members
+            .iter()
+            .map(|member| member.name.as_deref())
+            .collect::<Option<Vec<_>>>(),
+        _ => None,
+    };
+    let member_count = member_names.as_ref().map_or(0, Vec::len);
+    let arguments = order_arguments(expression, arguments, member_count, member_names.as_deref())?;
+
+    let members = arguments
+        .into_iter()
+        .enumerate()
+        .map(|(i, argument)| {
+            let member_type = types[typ].member_type(i).unwrap();
+            let Some(argument) = argument else {
+                return member_default_value(expression, typ, i, nodes, types, common_types)
+                    .ok_or_else(|| {
+                        let Type::Struct { ref members } = types[typ] else {
+                            unreachable!()
+                        };
+                        BindingError::MissingArgument {
+                            location: expression.get_location(),
+                            end_location: expression.get_end_location(),
+                            name: members[i].name.clone().unwrap(),
+                        }
+                    });
+            };
+
+            let argument = bind_expression(
+                argument,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                Some(member_type),
+            )?;
+            expect_type(argument, member_type, nodes, types)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(nodes.insert(BoundNode::Cast {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        to_type: typ,
+        from_expressions: members,
+    }))
+}
+
+/// The `BoundNode::Procedure` that a procedure value is if it is known at compile time, which is needed
+/// for the names and default values of its parameters, because they are not part of the procedure type
+fn known_procedure<'filepath>(
+    operand: NodeID<BoundNode<'filepath>>,
+    nodes: &Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'filepath>,
+) -> Result<Option<NodeID<BoundNode<'filepath>>>, BindingError<'filepath>> {
+    if !nodes[operand].is_constant(nodes) {
+        return Ok(None);
+    }
+    let Value::Procedure { procedure } = eval_bound_node(operand, nodes, types, common_types)?
+    else {
+        unreachable!()
+    };
+    Ok(procedure)
+}
+
+/// Puts the arguments of a call in the order of the parameters, an argument goes to the parameter at its position
+/// unless it is a named argument, and `None` is a parameter that was not given an argument
+fn order_arguments<'filepath, 'source, 'ast>(
+    expression: &Ast<'filepath, 'source>,
+    arguments: &'ast [Ast<'filepath, 'source>],
+    parameter_count: usize,
+    parameter_names: Option<&[&str]>,
+) -> Result<Vec<Option<&'ast Ast<'filepath, 'source>>>, BindingError<'filepath>> {
+    let mut ordered_arguments: Vec<Option<&Ast<'filepath, 'source>>> = vec![None; parameter_count];
+    for (i, argument) in arguments.iter().enumerate() {
+        let index = if let Ast::NamedArgument { ref name_token, .. } = *argument {
+            let TokenKind::Name(name) = name_token.kind else {
+                unreachable!()
+            };
+            let Some(parameter_names) = parameter_names else {
+                return Err(BindingError::InvalidNamedArgument {
+                    location: argument.get_location(),
+                    end_location: argument.get_end_location(),
+                });
+            };
+            parameter_names
+                .iter()
+                .position(|&parameter_name| parameter_name == name)
+                .ok_or_else(|| BindingError::UnknownArgumentName {
+                    location: name_token.get_location(),
+                    end_location: name_token.get_end_location(),
+                    name: name.to_string(),
+                })?
+        } else if i < parameter_count {
+            i
+        } else {
+            return Err(BindingError::WrongArgumentCount {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                expected: parameter_count,
+                got: arguments.len(),
+            });
+        };
+
+        // a positional argument after a named one can also go to a parameter that was already given an argument
+        if let Some(previous) = ordered_arguments[index] {
+            return Err(BindingError::DuplicateArgument {
+                location: argument.get_location(),
+                end_location: argument.get_end_location(),
+                previous_location: previous.get_location(),
+                previous_end_location: previous.get_end_location(),
+                name: parameter_names.unwrap()[index].to_string(),
+            });
+        }
+        ordered_arguments[index] = Some(argument);
+    }
+
+    Ok(ordered_arguments
+        .into_iter()
+        .map(|argument| {
+            argument.map(|argument| match *argument {
+                Ast::NamedArgument { ref value, .. } => &**value,
+                _ => argument,
+            })
+        })
+        .collect())
+}
+
+/// Binds the generic procedure with its compile time arguments as constants, or reuses the procedure that was
+/// bound for the same compile time arguments before, and returns it with the arguments that are left to pass to it
+#[allow(clippy::too_many_arguments)]
+fn instantiate_generic_procedure<'filepath, 'source, 'ast>(
+    expression: &Ast<'filepath, 'source>,
+    generic: NodeID<BoundNode<'filepath>>,
+    arguments: &'ast [Ast<'filepath, 'source>],
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<
+    (
+        NodeID<BoundNode<'filepath>>,
+        Vec<Option<&'ast Ast<'filepath, 'source>>>,
+    ),
+    BindingError<'filepath>,
+> {
+    let procedure = context.generic_procedures[&generic].procedure.clone();
+    let instance_names = &mut context.generic_procedures[&generic].names.clone();
+    let Ast::Procedure {
+        ref parameters,
+        ref return_type,
+        ref body,
+        ..
+    } = procedure
+    else {
+        unreachable!()
+    };
+    let parameter_names = parameters
+        .iter()
+        .map(|parameter| parameter_name(parameter))
+        .collect::<Vec<_>>();
+    let arguments = order_arguments(
+        expression,
+        arguments,
+        parameters.len(),
+        Some(&parameter_names),
+    )?;
+
+    // the arguments that are left out for the other parameters get their default values in the call to the instance
+    let mut compile_time_arguments = vec![];
+    let mut parameters_left = vec![];
+    let mut arguments_left = vec![];
+    for (parameter, argument) in parameters.iter().zip(arguments) {
+        if !is_compile_time_parameter(parameter) {
+            parameters_left.push(parameter);
+            arguments_left.push(argument);
+            continue;
+        }
+
+        let Ast::Declaration {
+            ref name_token,
+            ref typ,
+            ref value,
+            ..
+        } = *parameter
+        else {
+            unreachable!()
+        };
+
+        // later parameters can use the earlier compile time parameters, like `($T: type, value: T)`
+        let typ = bind_type(
+            typ.as_ref().unwrap(),
+            nodes,
+            types,
+            instance_names,
+            common_types,
+            context,
+        )?;
+        // the default value is bound where the procedure was written, so it can also use the earlier compile time parameters
+        let argument = match (argument, value) {
+            (Some(argument), _) => bind_expression(
+                argument,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                Some(typ),
+            )?,
+            (None, Some(value)) => bind_expression(
+                value,
+                nodes,
+                types,
+                &mut instance_names.clone(),
+                common_types,
+                context,
+                Some(typ),
+            )?,
+            (None, None) => {
+                return Err(BindingError::MissingArgument {
+                    location: expression.get_location(),
+                    end_location: expression.get_end_location(),
+                    name: parameter_name(parameter).to_string(),
+                })
+            }
+        };
+        let argument = expect_type(argument, typ, nodes, types)?;
+        if !nodes[argument].is_constant(nodes) {
+            return Err(expected_constant(argument, nodes));
+        }
+        let value = eval_bound_node(argument, nodes, types, common_types)?;
+        if value.has_address() {
+            return Err(BindingError::ConstantHasAddress {
+                location: nodes[argument].get_location(),
+                end_location: nodes[argument].get_end_location(),
+            });
+        }
+
+        let constant = nodes.insert(BoundNode::Constant {
+            location: parameter.get_location(),
+            end_location: parameter.get_end_location(),
+            typ,
+            value: value.clone(),
+        });
+        let TokenKind::Name(name) = name_token.kind else {
+            unreachable!()
+        };
+        instance_names.insert(name, constant);
+        compile_time_arguments.push(value);
+    }
+
+    if let Some(&instance) = context.generic_procedures[&generic]
+        .instances
+        .get(&compile_time_arguments)
+    {
+        return Ok((instance, arguments_left));
+    }
+    let instance = bind_procedure(
+        &procedure,
+        &parameters_left,
+        return_type.as_deref(),
+        body,
+        nodes,
+        types,
+        instance_names,
+        common_types,
+        context,
+        None,
+    )?;
+    context
+        .generic_procedures
+        .get_mut(&generic)
+        .unwrap()
+        .instances
+        .insert(compile_time_arguments, instance);
+    Ok((instance, arguments_left))
+}
+
+fn is_compile_time_parameter(parameter: &Ast<'_, '_>) -> bool {
+    matches!(
+        *parameter,
+        Ast::Declaration {
+            dollar_token: Some(_),
+            ..
+        }
+    )
+}
+
+/// Binds a procedure with the given parameters, which are all of the parameters of the procedure
+/// unless it is an instance of a generic procedure, the return type is inferred from the body if it is not given
+#[allow(clippy::too_many_arguments)]
+fn bind_procedure<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    parameters: &[&Ast<'filepath, 'source>],
+    return_type: Option<&Ast<'filepath, 'source>>,
+    body: &Ast<'filepath, 'source>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+    type_hint: Option<NodeID<Type>>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let parameter_names = parameters
+        .iter()
+        .map(|parameter| parameter_name(parameter))
+        .collect::<Vec<_>>();
+
+    let names = &mut names.clone();
+    // jumping out of a procedure and using the variables of the procedure around it is not allowed
+    names.retain(|_, &mut node| {
+        !matches!(
+            nodes[node],
+            BoundNode::Label { .. } | BoundNode::Declaration { .. }
+        )
+    });
+
+    let (hinted_parameters, hinted_return_type) = match type_hint.map(|typ| &types[typ]) {
+        Some(&Type::Procedure {
+            ref parameters,
+            return_type,
+        }) => (parameters.clone(), Some(return_type)),
+        _ => (vec![], None),
+    };
+
+    let parameters = parameters
+        .iter()
+        .enumerate()
+        .map(|(i, parameter)| {
+            let type_hint = hinted_parameters.get(i).copied();
+
+            if let Ast::Name { ref name_token } = **parameter {
+                let TokenKind::Name(name) = name_token.kind else {
+                    unreachable!()
+                };
+                let Some(typ) = type_hint else {
+                    return Err(BindingError::CannotInferParameterType {
+                        location: parameter.get_location(),
+                        end_location: parameter.get_end_location(),
+                        name: name.to_string(),
+                    });
+                };
+                let declaration = nodes.insert(BoundNode::Declaration {
+                    location: parameter.get_location(),
+                    end_location: parameter.get_end_location(),
+                    typ,
+                    value: None,
+                });
+                names.insert(name, declaration);
+                return Ok(declaration);
+            }
+
+            let parameter =
+                bind_declaration(parameter, true, nodes, types, names, common_types, context)?;
+
+            // the value of a parameter is its default value, which calls that leave out the argument get
+            let BoundNode::Declaration { value, .. } = nodes[parameter] else {
+                unreachable!()
+            };
+            if let Some(value) = value.filter(|&value| !nodes[value].is_constant(nodes)) {
+                return Err(expected_constant(value, nodes));
+            }
+
+            Ok(parameter)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (return_type, body) = if let Some(return_type) = return_type {
+        let return_type_location = return_type.get_location();
+        let return_type_end_location = return_type.get_end_location();
+        let return_type = bind_type(return_type, nodes, types, names, common_types, context)?;
+        let body = bind_expression(
+            body,
+            nodes,
+            types,
+            names,
+            common_types,
+            context,
+            Some(return_type),
+        )?;
+
+        let Some(body) = implicit_conversion(body, return_type, nodes, types) else {
+            return Err(BindingError::ProcedureBodyTypeMismatch {
+                location: nodes[body].get_location(),
+                end_location: nodes[body].get_end_location(),
+                return_type_location,
+                return_type_end_location,
+                return_type: types[return_type].pretty_print(types),
+                body_type: types[nodes[body].get_type(nodes)].pretty_print(types),
+            });
+        };
+        (return_type, body)
+    } else {
+        let body = bind_expression(
+            body,
+            nodes,
+            types,
+            names,
+            common_types,
+            context,
+            hinted_return_type,
+        )?;
+
+        // the return type of the type hint is used if the body can be converted to it,
+        // so that `(a) { a }` can be used as an `(a: int) -> int | bool`
+        match hinted_return_type.and_then(|return_type| {
+            Some((
+                return_type,
+                implicit_conversion(body, return_type, nodes, types)?,
+            ))
+        }) {
+            Some((return_type, body)) => (return_type, body),
+            None => (nodes[body].get_type(nodes), body),
+        }
+    };
+
+    let parameter_types = parameters
+        .iter()
+        .map(|&parameter| nodes[parameter].get_type(nodes))
+        .collect::<Vec<_>>();
+
+    let typ = type_hint
+        .and_then(|typ| {
+            if let Type::Procedure {
+                parameters: ref other_parameters,
+                return_type: other_return_type,
+            } = types[typ]
+            {
+                (other_return_type == return_type && other_parameters == &parameter_types)
+                    .then_some(typ)
+            } else {
+                None
+            }
+        })
+        .unwrap_or_else(|| common_types.get_procedure(types, &parameter_types, return_type));
+
+    let procedure = nodes.insert(BoundNode::Procedure {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        parameters,
+        return_type,
+        typ,
+        body,
+    });
+    context
+        .procedure_parameter_names
+        .insert(procedure, parameter_names);
+    Ok(procedure)
+}
+
+/// The name of a parameter, which is an `Ast::Declaration` or an `Ast::Name` if its type is inferred
+fn parameter_name<'source>(parameter: &Ast<'_, 'source>) -> &'source str {
+    let (Ast::Declaration { ref name_token, .. } | Ast::Name { ref name_token }) = *parameter
+    else {
+        unreachable!()
+    };
+    let TokenKind::Name(name) = name_token.kind else {
+        unreachable!()
+    };
+    name
+}
+
+/// Binds `operand.(index)` or `operand[index]` for the types that are not indexed with a type
+#[allow(clippy::too_many_arguments)]
+fn bind_index<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    operand: NodeID<BoundNode<'filepath>>,
+    index: &Ast<'filepath, 'source>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    let operand_type = nodes[operand].get_type(nodes);
+    let index = bind_expression(index, nodes, types, names, common_types, context, None)?;
+    let index_type = nodes[index].get_type(nodes);
+
+    // slices, dynamic arrays and multipointers only know their length at runtime, if at all
+    let length = match types[operand_type] {
+        Type::Slice { .. } | Type::DynamicArray { .. } | Type::Multipointer { .. } => None,
+        ref typ => Some(
+            typ.member_count()
+                .ok_or_else(|| BindingError::NotIndexable {
+                    location: nodes[operand].get_location(),
+                    end_location: nodes[operand].get_end_location(),
+                    typ: types[operand_type].pretty_print(types),
+                })?,
+        ),
+    };
+    if types[index_type].integer_range().is_none() {
+        return Err(BindingError::ExpectedIntegerIndex {
+            location: nodes[index].get_location(),
+            end_location: nodes[index].get_end_location(),
+            got: types[index_type].pretty_print(types),
+        });
+    }
+
+    // a constant index always gets the same member, so the exact type is known
+    if let Some(length) = length.filter(|_| nodes[index].is_constant(nodes)) {
+        let Value::Integer { value } = eval_bound_node(index, nodes, types, common_types)? else {
+            unreachable!()
+        };
+        let Some(member_index) = usize::try_from(value)
+            .ok()
+            .filter(|&member_index| member_index < length)
+        else {
+            return Err(BindingError::IndexOutOfBounds {
+                location: nodes[index].get_location(),
+                end_location: nodes[index].get_end_location(),
+                index: value,
+                length,
+            });
+        };
+        return Ok(nodes.insert(BoundNode::MemberAccess {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            operand,
+            member_index,
+            result_type: types[operand_type].member_type(member_index).unwrap(),
+        }));
+    }
+
+    let result_type = match types[operand_type] {
+        Type::Array { inner_type, .. }
+        | Type::Slice { inner_type }
+        | Type::DynamicArray { inner_type }
+        | Type::Multipointer {
+            pointed_to: inner_type,
+        } => inner_type,
+        Type::Struct { ref members } => {
+            let member_types = members.iter().map(|member| member.typ).collect::<Vec<_>>();
+            let result_type = common_types.get_union(types, &member_types);
+            return Ok(nodes.insert(BoundNode::StructIndex {
+                location: expression.get_location(),
+                end_location: expression.get_end_location(),
+                operand,
+                index,
+                result_type,
+            }));
+        }
+        _ => unreachable!(),
+    };
+    Ok(nodes.insert(BoundNode::Index {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        operand,
+        index,
+        result_type,
+    }))
+}
+
+/// Binds a call to a builtin procedure, whose arguments are checked here because they can be of any type
+#[allow(clippy::too_many_arguments)]
+fn bind_builtin_call<'filepath, 'source>(
+    expression: &Ast<'filepath, 'source>,
+    builtin: Builtin,
+    operand: NodeID<BoundNode<'filepath>>,
+    arguments: &[Ast<'filepath, 'source>],
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &mut Nodes<Type>,
+    names: &mut HashMap<&'source str, NodeID<BoundNode<'filepath>>>,
+    common_types: &mut CommonTypes<'filepath>,
+    context: &mut BindingContext<'filepath, 'source>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    // `append` can be given any number of values after the array
+    let expected_arguments = match builtin {
+        Builtin::Append | Builtin::Reserve | Builtin::Resize => 2,
+        Builtin::Pop | Builtin::Free => 1,
+    };
+    if arguments.len() != expected_arguments
+        && !(builtin == Builtin::Append && arguments.len() > expected_arguments)
+    {
+        return Err(BindingError::WrongArgumentCount {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            expected: expected_arguments,
+            got: arguments.len(),
+        });
+    }
+
+    let array = bind_expression(
+        &arguments[0],
+        nodes,
+        types,
+        names,
+        common_types,
+        context,
+        None,
+    )?;
+    let array_type = nodes[array].get_type(nodes);
+    let Type::DynamicArray { inner_type } = types[array_type] else {
+        return Err(BindingError::ExpectedDynamicArray {
+            location: nodes[array].get_location(),
+            end_location: nodes[array].get_end_location(),
+            got: types[array_type].pretty_print(types),
+        });
+    };
+    // the builtin changes the length and data of the array, so they are written back to where the array is
+    if !is_place(array, nodes, types) {
+        return Err(BindingError::ExpectedPlace {
+            location: nodes[array].get_location(),
+            end_location: nodes[array].get_end_location(),
+        });
+    }
+    // the elements that `resize` adds are the default value of the element type
+    if builtin == Builtin::Resize && !has_default_value(inner_type, types, common_types) {
+        return Err(BindingError::NoDefaultValue {
+            location: expression.get_location(),
+            end_location: expression.get_end_location(),
+            typ: types[inner_type].pretty_print(types),
+        });
+    }
+
+    let argument_type = match builtin {
+        Builtin::Append => inner_type,
+        Builtin::Reserve | Builtin::Resize => common_types.uint,
+        // these take no other arguments
+        Builtin::Pop | Builtin::Free => common_types.void,
+    };
+    let arguments = std::iter::once(Ok(array))
+        .chain(arguments[1..].iter().map(|argument| {
+            let argument = bind_expression(
+                argument,
+                nodes,
+                types,
+                names,
+                common_types,
+                context,
+                Some(argument_type),
+            )?;
+            expect_type(argument, argument_type, nodes, types)
+        }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(nodes.insert(BoundNode::Call {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        operand,
+        arguments,
+        result_type: match builtin {
+            Builtin::Pop => inner_type,
+            Builtin::Append | Builtin::Reserve | Builtin::Resize | Builtin::Free => {
+                common_types.void
+            }
+        },
+    }))
+}
+
+/// Whether `node` refers to a value in memory, which can have its address taken and be modified
+fn is_place<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    nodes: &Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+) -> bool {
+    match nodes[node] {
+        BoundNode::Name {
+            referenced_node, ..
+        } => matches!(nodes[referenced_node], BoundNode::Declaration { .. }),
+        BoundNode::Dereference { .. } => true,
+        // the members of slices and dynamic arrays are not stored as separate values
+        BoundNode::MemberAccess { operand, .. } => {
+            matches!(
+                types[nodes[operand].get_type(nodes)],
+                Type::Struct { .. } | Type::Array { .. }
+            ) && is_place(operand, nodes, types)
+        }
+        // the elements of everything but an array are always in memory
+        BoundNode::Index { operand, .. } => {
+            !matches!(types[nodes[operand].get_type(nodes)], Type::Array { .. })
+                || is_place(operand, nodes, types)
+        }
+        _ => false,
+    }
+}
+
+/// Same as `implicit_conversion`, but it is an error if the conversion is not possible
+fn expect_type<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    typ: NodeID<Type>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    implicit_conversion(node, typ, nodes, types).ok_or_else(|| BindingError::TypeMismatch {
+        location: nodes[node].get_location(),
+        end_location: nodes[node].get_end_location(),
+        expected: types[typ].pretty_print(types),
+        got: types[nodes[node].get_type(nodes)].pretty_print(types),
+        is_rawptr: matches!(types[nodes[node].get_type(nodes)], Type::RawPtr),
+    })
+}
+
+/// Returns `node` converted to `typ`, if its type can be implicitly converted to `typ`
+fn implicit_conversion<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    typ: NodeID<Type>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+) -> Option<NodeID<BoundNode<'filepath>>> {
+    let node_type = nodes[node].get_type(nodes);
+    if node_type == typ || matches!(types[node_type], Type::Never) {
+        return Some(node);
+    }
+
+    match types[typ] {
+        // values go into any union that has their type, and unions into any union that has all of their variants
+        Type::Union { ref variants } => {
+            let is_variant = match types[node_type] {
+                Type::Union {
+                    variants: ref node_variants,
+                } => node_variants
+                    .iter()
+                    .all(|variant| variants.contains(variant)),
+                _ => variants.contains(&node_type),
+            };
+            is_variant.then(|| {
+                nodes.insert(BoundNode::Cast {
+                    location: nodes[node].get_location(),
+                    end_location: nodes[node].get_end_location(),
+                    to_type: typ,
+                    from_expressions: vec![node],
+                })
+            })
+        }
+        // any pointer can be used as a raw pointer, but getting the typed pointer back needs a cast
+        Type::RawPtr => matches!(
+            types[node_type],
+            Type::Pointer { .. } | Type::Multipointer { .. }
+        )
+        .then(|| {
+            nodes.insert(BoundNode::Cast {
+                location: nodes[node].get_location(),
+                end_location: nodes[node].get_end_location(),
+                to_type: typ,
+                from_expressions: vec![node],
+            })
+        }),
+        // a dynamic array can be used as a slice of the values that are in it
+        Type::Slice { inner_type } => matches!(
+            types[node_type],
+            Type::DynamicArray { inner_type: node_inner_type } if node_inner_type == inner_type
+        )
+        .then(|| {
+            nodes.insert(BoundNode::Cast {
+                location: nodes[node].get_location(),
+                end_location: nodes[node].get_end_location(),
+                to_type: typ,
+                from_expressions: vec![node],
+            })
+        }),
+        _ => None,
+    }
+}
+
+/// Whether a value of type `from` can be converted to `to` with a cast, like `to(value)`
+fn is_explicit_conversion(from: NodeID<Type>, to: NodeID<Type>, types: &Nodes<Type>) -> bool {
+    match (&types[to], &types[from]) {
+        // integers are wrapped around into the range of the type they are cast to
+        (to, from) if to.integer_range().is_some() && from.integer_range().is_some() => true,
+        (Type::Bool, from) if from.integer_range().is_some() => true,
+        (to, Type::Bool) if to.integer_range().is_some() => true,
+        // pointers and multipointers are stored the same way
+        (
+            &Type::Pointer { pointed_to },
+            &Type::Multipointer {
+                pointed_to: from_pointed_to,
+            },
+        )
+        | (
+            &Type::Multipointer { pointed_to },
+            &Type::Pointer {
+                pointed_to: from_pointed_to,
+            },
+        ) => pointed_to == from_pointed_to,
+        // nothing checks that a raw pointer points to the type it is cast to
+        (Type::Pointer { .. } | Type::Multipointer { .. }, Type::RawPtr) => true,
+        _ => false,
+    }
+}
+
+/// Replaces `node` with its value if it can be evaluated at compile time
+fn fold_constant<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'filepath>,
+    context: &BindingContext<'filepath, '_>,
+) -> Result<NodeID<BoundNode<'filepath>>, BindingError<'filepath>> {
+    if context.might_not_run || !nodes[node].is_constant(nodes) {
+        return Ok(node);
+    }
+
+    let value = eval_bound_node(node, nodes, types, common_types)?;
+    // the memory would not exist anymore, so the value is evaluated at runtime instead
+    if value.has_address() {
+        return Ok(node);
+    }
+    Ok(nodes.insert(BoundNode::Constant {
+        location: nodes[node].get_location(),
+        end_location: nodes[node].get_end_location(),
+        typ: nodes[node].get_type(nodes),
+        value,
+    }))
+}
+
+/// The default values of the members of a struct type that come after the first `count`,
+/// returns `None` if any of them does not have a default value
+fn trailing_default_values<'filepath>(
+    expression: &Ast<'filepath, '_>,
+    typ: NodeID<Type>,
+    count: usize,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'filepath>,
+) -> Option<Vec<NodeID<BoundNode<'filepath>>>> {
+    let trailing = common_types
+        .struct_default_values
+        .get(&typ)?
+        .get(count..)?
+        .len();
+    (count..count + trailing)
+        .map(|i| member_default_value(expression, typ, i, nodes, types, common_types))
+        .collect()
+}
+
+/// A constant with the default value of the member `index` of a struct type, at the location of `expression`
+/// that leaves it out, returns `None` if the member does not have a default value
+fn member_default_value<'filepath>(
+    expression: &Ast<'filepath, '_>,
+    typ: NodeID<Type>,
+    index: usize,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'filepath>,
+) -> Option<NodeID<BoundNode<'filepath>>> {
+    let value = common_types.struct_default_values.get(&typ)?[index].clone()?;
+    Some(nodes.insert(BoundNode::Constant {
+        location: expression.get_location(),
+        end_location: expression.get_end_location(),
+        typ: types[typ].member_type(index).unwrap(),
+        value,
+    }))
+}
+
+/// Converts each of `values` to the type of the matching member of `typ`, returns `None`
+/// if it is not a struct or array type with a member for each value that it can be converted to
+fn convert_members<'filepath>(
+    values: &[NodeID<BoundNode<'filepath>>],
+    typ: NodeID<Type>,
+    nodes: &mut Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+) -> Option<Vec<NodeID<BoundNode<'filepath>>>> {
+    if types[typ].member_count() != Some(values.len()) {
+        return None;
+    }
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| implicit_conversion(value, types[typ].member_type(i)?, nodes, types))
+        .collect()
+}
+
+/// Whether a value of `typ` can be created without being given, like for a declaration without a value,
+/// which is not the case for `never` and `label`, or for any type that holds one of them,
+/// and for unions, because none of their variants is the one that they would obviously hold
+fn has_default_value(
+    typ: NodeID<Type>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'_>,
+) -> bool {
+    match types[typ] {
+        Type::Never
+        | Type::Label
+        | Type::Union { .. }
+        | Type::Builtin
+        | Type::GenericProcedure => false,
+        Type::Array { inner_type, .. } | Type::DynamicArray { inner_type } => {
+            has_default_value(inner_type, types, common_types)
+        }
+        Type::Struct { ref members } => {
+            let default_values = common_types.struct_default_values.get(&typ);
+            members.iter().enumerate().all(|(i, member)| {
+                default_values.is_some_and(|default_values| default_values[i].is_some())
+                    || has_default_value(member.typ, types, common_types)
+            })
+        }
+        _ => true,
+    }
+}
+
+fn expected_constant<'filepath>(
+    node: NodeID<BoundNode<'filepath>>,
+    nodes: &Nodes<BoundNode<'filepath>>,
+) -> BindingError<'filepath> {
+    let declaration = match nodes[node] {
+        BoundNode::Name {
+            referenced_node, ..
+        } if matches!(nodes[referenced_node], BoundNode::Declaration { .. }) => Some((
+            nodes[referenced_node].get_location(),
+            nodes[referenced_node].get_end_location(),
+        )),
+        _ => None,
+    };
+    BindingError::ExpectedConstant {
+        location: nodes[node].get_location(),
+        end_location: nodes[node].get_end_location(),
+        declaration,
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum BindingError<'filepath> {
+    #[display(fmt = "Only constants are allowed in the global scope")]
+    OnlyConstantsInGlobalScope {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "{_0}")]
+    EvalError(EvalError<'filepath>),
+    #[display(fmt = "The call fails when it is evaluated at compile time: {error}")]
+    CompileTimeCallFailed {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        error: EvalError<'filepath>,
+    },
+    #[display(
+        fmt = "The integer literal {}{value} does not fit in the type {typ}",
+        "if *negated { \"-\" } else { \"\" }"
+    )]
+    IntegerLiteralOutOfRange {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        value: u128,
+        /// Whether the literal is the operand of a `-`
+        negated: bool,
+        typ: String,
+        range: RangeInclusive<i128>,
+    },
+    #[display(fmt = "Unknown name '{name}'")]
+    UnknownName {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "Expected a value that is known at compile time")]
+    ExpectedConstant {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        /// Where the variable was declared, if the value is the name of a variable
+        declaration: Option<(SourceLocation<'filepath>, SourceLocation<'filepath>)>,
+    },
+    #[display(fmt = "Expected a type, but got a value of type {got}")]
+    ExpectedType {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        got: String,
+    },
+    #[display(
+        fmt = "The procedure body has type {body_type}, but the procedure returns {return_type}"
+    )]
+    ProcedureBodyTypeMismatch {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        return_type_location: SourceLocation<'filepath>,
+        return_type_end_location: SourceLocation<'filepath>,
+        return_type: String,
+        body_type: String,
+    },
+    #[display(fmt = "The type {typ} has no member called '{name}'")]
+    UnknownMember {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+        name: String,
+    },
+    #[display(fmt = "Cannot cast ({from_types}) to {to_type}")]
+    InvalidCast {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        from_types: String,
+        to_type: String,
+    },
+    #[display(fmt = "A value of type {typ} cannot be called")]
+    NotCallable {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+    },
+    #[display(fmt = "Expected type {expected}, but got {got}")]
+    TypeMismatch {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        expected: String,
+        got: String,
+        /// Whether the type that was got is `rawptr`, which has to be cast before it is used
+        is_rawptr: bool,
+    },
+    #[display(
+        fmt = "The branches of the conditional have different types, {then_type} and {else_type}"
+    )]
+    MismatchedBranchTypes {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        then_location: SourceLocation<'filepath>,
+        then_end_location: SourceLocation<'filepath>,
+        else_location: SourceLocation<'filepath>,
+        else_end_location: SourceLocation<'filepath>,
+        then_type: String,
+        else_type: String,
+    },
+    #[display(fmt = "Labels can only be declared directly inside of a block with `name: label`")]
+    InvalidLabelDeclaration {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Labels can only be used as the target of `->`")]
+    LabelUsedAsValue {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        label_location: SourceLocation<'filepath>,
+        label_end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "There is no label called '{name}' in an enclosing block")]
+    UnknownLabel {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "The type {typ} has no default value, so a value must be given")]
+    NoDefaultValue {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+    },
+    #[display(fmt = "The struct already has a member called '{name}'")]
+    DuplicateMemberName {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        previous_location: SourceLocation<'filepath>,
+        previous_end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "A value of type {typ} cannot be indexed")]
+    NotIndexable {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+    },
+    #[display(fmt = "A value of type {typ} cannot be sliced")]
+    NotSliceable {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+    },
+    #[display(fmt = "A multipointer has no length, so the end of the slice must be given")]
+    ExpectedSliceEnd {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Expected an integer index, but got {got}")]
+    ExpectedIntegerIndex {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        got: String,
+    },
+    #[display(fmt = "The index {index} is out of bounds for a length of {length}")]
+    IndexOutOfBounds {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        index: i128,
+        length: usize,
+    },
+    #[display(fmt = "Expected {expected} arguments, but got {got}")]
+    WrongArgumentCount {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        expected: usize,
+        got: usize,
+    },
+    #[display(
+        fmt = "A compile time value cannot point to memory, because that memory does not exist at runtime"
+    )]
+    ConstantHasAddress {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "The parameters of a procedure type must have a type")]
+    ExpectedParameterType {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Only the parameters of a procedure value can be compile time parameters")]
+    InvalidCompileTimeParameter {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(
+        fmt = "The type of the parameter '{name}' cannot be inferred, because no procedure type is expected here"
+    )]
+    CannotInferParameterType {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(
+        fmt = "The parameters of a procedure type cannot have default values, because they are not part of the type"
+    )]
+    DefaultValueInProcedureType {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(
+        fmt = "Named arguments can only be given to a struct type or a procedure that is known at compile time"
+    )]
+    InvalidNamedArgument {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "There is no parameter or member called '{name}'")]
+    UnknownArgumentName {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "There is already an argument for '{name}'")]
+    DuplicateArgument {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        previous_location: SourceLocation<'filepath>,
+        previous_end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "There is no argument for '{name}', which does not have a default value")]
+    MissingArgument {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "The constant '{name}' is used in its own value before its type is known")]
+    RecursiveConstant {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        name: String,
+    },
+    #[display(fmt = "Expected a dynamic array, but got {got}")]
+    ExpectedDynamicArray {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        got: String,
+    },
+    #[display(fmt = "Expected a place in memory, like a variable or a dereferenced pointer")]
+    ExpectedPlace {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "A value of type {typ} cannot be dereferenced")]
+    NotAPointer {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+        /// Whether the type is `rawptr`, which has to be cast before it is dereferenced
+        is_rawptr: bool,
+    },
+    #[display(fmt = "The union {union_type} cannot hold the type {variant}")]
+    NotAUnionVariant {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        union_type: String,
+        variant: String,
+    },
+    #[display(fmt = "Cannot apply the unary operator {operator} to {operand_type}")]
+    InvalidUnaryOperator {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operator: UnaryOperator,
+        operand_type: String,
+    },
+    #[display(fmt = "Cannot apply the binary operator {operator} to {left_type} and {right_type}")]
+    InvalidBinaryOperator {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operator: BinaryOperator,
+        left_type: String,
+        right_type: String,
+    },
+}
+
+impl<'filepath> GetLocation<'filepath> for BindingError<'filepath> {
+    fn get_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            BindingError::EvalError(ref error) => error.get_location(),
+            BindingError::OnlyConstantsInGlobalScope { location, .. }
+            | BindingError::CompileTimeCallFailed { location, .. }
+            | BindingError::IntegerLiteralOutOfRange { location, .. }
+            | BindingError::UnknownName { location, .. }
+            | BindingError::ExpectedConstant { location, .. }
+            | BindingError::ExpectedType { location, .. }
+            | BindingError::ProcedureBodyTypeMismatch { location, .. }
+            | BindingError::UnknownMember { location, .. }
+            | BindingError::InvalidCast { location, .. }
+            | BindingError::NotCallable { location, .. }
+            | BindingError::TypeMismatch { location, .. }
+            | BindingError::MismatchedBranchTypes { location, .. }
+            | BindingError::InvalidLabelDeclaration { location, .. }
+            | BindingError::LabelUsedAsValue { location, .. }
+            | BindingError::UnknownLabel { location, .. }
+            | BindingError::NoDefaultValue { location, .. }
+            | BindingError::DuplicateMemberName { location, .. }
+            | BindingError::NotIndexable { location, .. }
+            | BindingError::NotSliceable { location, .. }
+            | BindingError::ExpectedSliceEnd { location, .. }
+            | BindingError::ExpectedIntegerIndex { location, .. }
+            | BindingError::IndexOutOfBounds { location, .. }
+            | BindingError::WrongArgumentCount { location, .. }
+            | BindingError::ConstantHasAddress { location, .. }
+            | BindingError::ExpectedParameterType { location, .. }
+            | BindingError::InvalidCompileTimeParameter { location, .. }
+            | BindingError::CannotInferParameterType { location, .. }
+            | BindingError::DefaultValueInProcedureType { location, .. }
+            | BindingError::InvalidNamedArgument { location, .. }
+            | BindingError::UnknownArgumentName { location, .. }
+            | BindingError::DuplicateArgument { location, .. }
+            | BindingError::MissingArgument { location, .. }
+            | BindingError::RecursiveConstant { location, .. }
+            | BindingError::ExpectedDynamicArray { location, .. }
+            | BindingError::ExpectedPlace { location, .. }
+            | BindingError::NotAPointer { location, .. }
+            | BindingError::NotAUnionVariant { location, .. }
+            | BindingError::InvalidUnaryOperator { location, .. }
+            | BindingError::InvalidBinaryOperator { location, .. } => location,
+        }
+    }
+
+    fn get_end_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            BindingError::EvalError(ref error) => error.get_end_location(),
+            BindingError::OnlyConstantsInGlobalScope { end_location, .. }
+            | BindingError::CompileTimeCallFailed { end_location, .. }
+            | BindingError::IntegerLiteralOutOfRange { end_location, .. }
+            | BindingError::UnknownName { end_location, .. }
+            | BindingError::ExpectedConstant { end_location, .. }
+            | BindingError::ExpectedType { end_location, .. }
+            | BindingError::ProcedureBodyTypeMismatch { end_location, .. }
+            | BindingError::UnknownMember { end_location, .. }
+            | BindingError::InvalidCast { end_location, .. }
+            | BindingError::NotCallable { end_location, .. }
+            | BindingError::TypeMismatch { end_location, .. }
+            | BindingError::MismatchedBranchTypes { end_location, .. }
+            | BindingError::InvalidLabelDeclaration { end_location, .. }
+            | BindingError::LabelUsedAsValue { end_location, .. }
+            | BindingError::UnknownLabel { end_location, .. }
+            | BindingError::NoDefaultValue { end_location, .. }
+            | BindingError::DuplicateMemberName { end_location, .. }
+            | BindingError::NotIndexable { end_location, .. }
+            | BindingError::NotSliceable { end_location, .. }
+            | BindingError::ExpectedSliceEnd { end_location, .. }
+            | BindingError::ExpectedIntegerIndex { end_location, .. }
+            | BindingError::IndexOutOfBounds { end_location, .. }
+            | BindingError::WrongArgumentCount { end_location, .. }
+            | BindingError::ConstantHasAddress { end_location, .. }
+            | BindingError::ExpectedParameterType { end_location, .. }
+            | BindingError::InvalidCompileTimeParameter { end_location, .. }
+            | BindingError::CannotInferParameterType { end_location, .. }
+            | BindingError::DefaultValueInProcedureType { end_location, .. }
+            | BindingError::InvalidNamedArgument { end_location, .. }
+            | BindingError::UnknownArgumentName { end_location, .. }
+            | BindingError::DuplicateArgument { end_location, .. }
+            | BindingError::MissingArgument { end_location, .. }
+            | BindingError::RecursiveConstant { end_location, .. }
+            | BindingError::ExpectedDynamicArray { end_location, .. }
+            | BindingError::ExpectedPlace { end_location, .. }
+            | BindingError::NotAPointer { end_location, .. }
+            | BindingError::NotAUnionVariant { end_location, .. }
+            | BindingError::InvalidUnaryOperator { end_location, .. }
+            | BindingError::InvalidBinaryOperator { end_location, .. } => end_location,
+        }
+    }
+}
+
+impl<'filepath> From<EvalError<'filepath>> for BindingError<'filepath> {
+    fn from(error: EvalError<'filepath>) -> Self {
+        BindingError::EvalError(error)
+    }
 }
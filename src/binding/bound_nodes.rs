@@ -4,6 +4,67 @@ use crate::{
     nodes::{NodeID, Nodes},
     tokens::{GetLocation, SourceLocation},
 };
+use derive_more::Display;
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    #[display(fmt = "+")]
+    Identity,
+    #[display(fmt = "-")]
+    Negate,
+    #[display(fmt = "!")]
+    LogicalNot,
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    #[display(fmt = "+")]
+    Add,
+    #[display(fmt = "-")]
+    Subtract,
+    #[display(fmt = "*")]
+    Multiply,
+    #[display(fmt = "/")]
+    Divide,
+    #[display(fmt = "%")]
+    Remainder,
+    #[display(fmt = "==")]
+    Equal,
+    #[display(fmt = "!=")]
+    NotEqual,
+    #[display(fmt = "<")]
+    LessThan,
+    #[display(fmt = "<=")]
+    LessThanEqual,
+    #[display(fmt = ">")]
+    GreaterThan,
+    #[display(fmt = ">=")]
+    GreaterThanEqual,
+    #[display(fmt = "&&")]
+    LogicalAnd,
+    #[display(fmt = "||")]
+    LogicalOr,
+}
+
+/// The procedures that are built into the language, because they cannot be written in it
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum Builtin {
+    /// `append(array, values...)` adds the values to the end of a dynamic array
+    #[display(fmt = "append")]
+    Append,
+    /// `reserve(array, capacity)` makes sure a dynamic array can hold `capacity` values without reallocating
+    #[display(fmt = "reserve")]
+    Reserve,
+    /// `resize(array, length)` changes the length of a dynamic array, new values are set to their default value
+    #[display(fmt = "resize")]
+    Resize,
+    /// `pop(array)` removes the last value of a dynamic array and returns it
+    #[display(fmt = "pop")]
+    Pop,
+    /// `free(array)` frees the memory of a dynamic array and makes it empty
+    #[display(fmt = "free")]
+    Free,
+}
 
 #[derive(Debug, Clone)]
 pub enum BoundNode<'filepath> {
@@ -19,12 +80,28 @@ pub enum BoundNode<'filepath> {
         typ: NodeID<Type>,
         value: Value<'filepath>,
     },
+    /// A constant that is declared but whose value has not been bound yet, it is replaced with
+    /// a `BoundNode::Constant` once it is, so it is only used in the value of the constant itself,
+    /// like in a procedure that calls itself
+    UnboundConstant {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        /// `None` until the type is known, which is before the value is bound if it is a procedure with
+        /// the types of its parameters and its return type written out
+        typ: Option<NodeID<Type>>,
+    },
     Declaration {
         location: SourceLocation<'filepath>,
         end_location: SourceLocation<'filepath>,
         typ: NodeID<Type>,
         value: Option<NodeID<BoundNode<'filepath>>>,
     },
+    Integer {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: NodeID<Type>,
+        value: u128,
+    },
     Type {
         location: SourceLocation<'filepath>,
         end_location: SourceLocation<'filepath>,
@@ -36,6 +113,58 @@ pub enum BoundNode<'filepath> {
         end_location: SourceLocation<'filepath>,
         referenced_node: NodeID<BoundNode<'filepath>>,
     },
+    Unary {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operator: UnaryOperator,
+        operand: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    /// Gets a pointer to a place in memory, which is checked to be a place by the binder
+    AddressOf {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operand: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    Dereference {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operand: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    Binary {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        left: NodeID<BoundNode<'filepath>>,
+        operator: BinaryOperator,
+        right: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    /// Writes `value` to `place`, or applies `operator` to them first for a compound assignment like `place += value`
+    Assignment {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        place: NodeID<BoundNode<'filepath>>,
+        operator: Option<BinaryOperator>,
+        value: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    If {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        condition: NodeID<BoundNode<'filepath>>,
+        then_expression: NodeID<BoundNode<'filepath>>,
+        else_expression: Option<NodeID<BoundNode<'filepath>>>,
+        result_type: NodeID<Type>,
+    },
+    While {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        condition: NodeID<BoundNode<'filepath>>,
+        body: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
     MemberAccess {
         location: SourceLocation<'filepath>,
         end_location: SourceLocation<'filepath>,
@@ -43,6 +172,40 @@ pub enum BoundNode<'filepath> {
         member_index: usize,
         result_type: NodeID<Type>,
     },
+    /// Gets the member of a struct at an index that is only known at runtime,
+    /// the result is a union of the member types
+    StructIndex {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operand: NodeID<BoundNode<'filepath>>,
+        index: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    /// Gets an element of an array at an index that is only known at runtime,
+    /// or an element of a slice, a dynamic array or a multipointer
+    Index {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operand: NodeID<BoundNode<'filepath>>,
+        index: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    /// Gets a slice of the elements from `low` up to `high`, which default to the start and the end
+    Slice {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operand: NodeID<BoundNode<'filepath>>,
+        low: Option<NodeID<BoundNode<'filepath>>>,
+        high: Option<NodeID<BoundNode<'filepath>>>,
+        result_type: NodeID<Type>,
+    },
+    /// Gets the value out of a union, which fails at runtime if the union holds a different variant
+    UnionExtract {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        operand: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
     Call {
         location: SourceLocation<'filepath>,
         end_location: SourceLocation<'filepath>,
@@ -64,6 +227,30 @@ pub enum BoundNode<'filepath> {
         typ: NodeID<Type>,
         body: NodeID<BoundNode<'filepath>>,
     },
+    /// A procedure with compile time parameters, which is bound again for each set of compile time arguments
+    /// that it is called with
+    GenericProcedure {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: NodeID<Type>,
+    },
+    Label {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        result_type: NodeID<Type>,
+    },
+    Goto {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        label: NodeID<BoundNode<'filepath>>,
+        result_type: NodeID<Type>,
+    },
+    /// An expression that failed to parse, it has the type `never` so that it does not cause more errors
+    Error {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        result_type: NodeID<Type>,
+    },
 }
 
 impl<'filepath> BoundNode<'filepath> {
@@ -73,12 +260,49 @@ impl<'filepath> BoundNode<'filepath> {
                 ref expressions, ..
             } => expressions.iter().all(|&id| nodes[id].is_constant(nodes)),
             BoundNode::Constant { .. } => true,
+            // the value does not exist yet
+            BoundNode::UnboundConstant { .. } => false,
             BoundNode::Declaration { .. } => false,
+            BoundNode::Integer { .. } => true,
             BoundNode::Type { .. } => true,
             BoundNode::Name {
                 referenced_node, ..
             } => nodes[referenced_node].is_constant(nodes),
+            BoundNode::Unary { operand, .. } => nodes[operand].is_constant(nodes),
+            // the memory that is pointed to does not exist anymore after compile time evaluation
+            BoundNode::AddressOf { .. } => false,
+            BoundNode::Dereference { operand, .. } => nodes[operand].is_constant(nodes),
+            // the place is always a variable or memory, which do not exist at compile time
+            BoundNode::Assignment { .. } => false,
+            BoundNode::Binary { left, right, .. } => {
+                nodes[left].is_constant(nodes) && nodes[right].is_constant(nodes)
+            }
+            BoundNode::If {
+                condition,
+                then_expression,
+                else_expression,
+                ..
+            } => {
+                nodes[condition].is_constant(nodes)
+                    && nodes[then_expression].is_constant(nodes)
+                    && else_expression.is_none_or(|id| nodes[id].is_constant(nodes))
+            }
+            BoundNode::While {
+                condition, body, ..
+            } => nodes[condition].is_constant(nodes) && nodes[body].is_constant(nodes),
             BoundNode::MemberAccess { operand, .. } => nodes[operand].is_constant(nodes),
+            BoundNode::UnionExtract { operand, .. } => nodes[operand].is_constant(nodes),
+            BoundNode::Slice {
+                operand, low, high, ..
+            } => {
+                nodes[operand].is_constant(nodes)
+                    && low.is_none_or(|id| nodes[id].is_constant(nodes))
+                    && high.is_none_or(|id| nodes[id].is_constant(nodes))
+            }
+            BoundNode::StructIndex { operand, index, .. }
+            | BoundNode::Index { operand, index, .. } => {
+                nodes[operand].is_constant(nodes) && nodes[index].is_constant(nodes)
+            }
             BoundNode::Call {
                 operand,
                 ref arguments,
@@ -94,6 +318,10 @@ impl<'filepath> BoundNode<'filepath> {
                 .iter()
                 .all(|&expression| nodes[expression].is_constant(nodes)),
             BoundNode::Procedure { .. } => true,
+            BoundNode::GenericProcedure { .. } => true,
+            BoundNode::Label { .. } => true,
+            BoundNode::Goto { .. } => false,
+            BoundNode::Error { .. } => false,
         }
     }
 
@@ -101,15 +329,32 @@ impl<'filepath> BoundNode<'filepath> {
         match *self {
             BoundNode::Block { result_type, .. } => result_type,
             BoundNode::Constant { typ, .. } => typ,
+            BoundNode::UnboundConstant { typ, .. } => typ.unwrap(),
             BoundNode::Declaration { typ, .. } => typ,
+            BoundNode::Integer { typ, .. } => typ,
             BoundNode::Type { type_type, .. } => type_type,
             BoundNode::Name {
                 referenced_node, ..
             } => nodes[referenced_node].get_type(nodes),
+            BoundNode::Unary { result_type, .. } => result_type,
+            BoundNode::AddressOf { result_type, .. } => result_type,
+            BoundNode::Dereference { result_type, .. } => result_type,
+            BoundNode::Binary { result_type, .. } => result_type,
+            BoundNode::Assignment { result_type, .. } => result_type,
+            BoundNode::If { result_type, .. } => result_type,
+            BoundNode::While { result_type, .. } => result_type,
             BoundNode::MemberAccess { result_type, .. } => result_type,
+            BoundNode::StructIndex { result_type, .. } => result_type,
+            BoundNode::Index { result_type, .. } => result_type,
+            BoundNode::Slice { result_type, .. } => result_type,
+            BoundNode::UnionExtract { result_type, .. } => result_type,
             BoundNode::Call { result_type, .. } => result_type,
             BoundNode::Cast { to_type, .. } => to_type,
             BoundNode::Procedure { typ, .. } => typ,
+            BoundNode::GenericProcedure { typ, .. } => typ,
+            BoundNode::Label { result_type, .. } => result_type,
+            BoundNode::Goto { result_type, .. } => result_type,
+            BoundNode::Error { result_type, .. } => result_type,
         }
     }
 }
@@ -119,13 +364,30 @@ impl<'filepath> GetLocation<'filepath> for BoundNode<'filepath> {
         match *self {
             BoundNode::Block { location, .. }
             | BoundNode::Constant { location, .. }
+            | BoundNode::UnboundConstant { location, .. }
             | BoundNode::Declaration { location, .. }
+            | BoundNode::Integer { location, .. }
             | BoundNode::Type { location, .. }
             | BoundNode::Name { location, .. }
+            | BoundNode::Unary { location, .. }
+            | BoundNode::AddressOf { location, .. }
+            | BoundNode::Dereference { location, .. }
+            | BoundNode::Binary { location, .. }
+            | BoundNode::Assignment { location, .. }
+            | BoundNode::If { location, .. }
+            | BoundNode::While { location, .. }
             | BoundNode::MemberAccess { location, .. }
+            | BoundNode::StructIndex { location, .. }
+            | BoundNode::Index { location, .. }
+            | BoundNode::Slice { location, .. }
+            | BoundNode::UnionExtract { location, .. }
             | BoundNode::Call { location, .. }
             | BoundNode::Cast { location, .. }
-            | BoundNode::Procedure { location, .. } => location,
+            | BoundNode::Procedure { location, .. }
+            | BoundNode::GenericProcedure { location, .. }
+            | BoundNode::Label { location, .. }
+            | BoundNode::Goto { location, .. }
+            | BoundNode::Error { location, .. } => location,
         }
     }
 
@@ -133,13 +395,30 @@ impl<'filepath> GetLocation<'filepath> for BoundNode<'filepath> {
         match *self {
             BoundNode::Block { end_location, .. }
             | BoundNode::Constant { end_location, .. }
+            | BoundNode::UnboundConstant { end_location, .. }
             | BoundNode::Declaration { end_location, .. }
+            | BoundNode::Integer { end_location, .. }
             | BoundNode::Type { end_location, .. }
             | BoundNode::Name { end_location, .. }
+            | BoundNode::Unary { end_location, .. }
+            | BoundNode::AddressOf { end_location, .. }
+            | BoundNode::Dereference { end_location, .. }
+            | BoundNode::Binary { end_location, .. }
+            | BoundNode::Assignment { end_location, .. }
+            | BoundNode::If { end_location, .. }
+            | BoundNode::While { end_location, .. }
             | BoundNode::MemberAccess { end_location, .. }
+            | BoundNode::StructIndex { end_location, .. }
+            | BoundNode::Index { end_location, .. }
+            | BoundNode::Slice { end_location, .. }
+            | BoundNode::UnionExtract { end_location, .. }
             | BoundNode::Call { end_location, .. }
             | BoundNode::Cast { end_location, .. }
-            | BoundNode::Procedure { end_location, .. } => end_location,
+            | BoundNode::Procedure { end_location, .. }
+            | BoundNode::GenericProcedure { end_location, .. }
+            | BoundNode::Label { end_location, .. }
+            | BoundNode::Goto { end_location, .. }
+            | BoundNode::Error { end_location, .. } => end_location,
         }
     }
 }
@@ -1,27 +1,51 @@
 use crate::{
-    binding::Type,
+    binding::{StructMember, Type},
+    eval::Value,
     nodes::{NodeID, Nodes},
 };
 use std::collections::HashMap;
 
 #[allow(clippy::type_complexity)]
-pub struct CommonTypes {
+pub struct CommonTypes<'filepath> {
     pub typ: NodeID<Type>,
     pub void: NodeID<Type>,
+    pub never: NodeID<Type>,
+    pub label: NodeID<Type>,
+    pub bool: NodeID<Type>,
     pub int: NodeID<Type>,
     pub uint: NodeID<Type>,
     pub u8: NodeID<Type>,
+    pub u16: NodeID<Type>,
+    pub u32: NodeID<Type>,
+    pub u64: NodeID<Type>,
+    pub s8: NodeID<Type>,
+    pub s16: NodeID<Type>,
+    pub s32: NodeID<Type>,
+    pub s64: NodeID<Type>,
+    pub rawptr: NodeID<Type>,
+    pub generic_procedure: NodeID<Type>,
     /// HashMap from `inner_type` to `Slice { inner_type }`
     pub slice_types: HashMap<NodeID<Type>, NodeID<Type>>,
+    /// HashMap from `(length, inner_type)` to `Array { length, inner_type }`
+    pub array_types: HashMap<(usize, NodeID<Type>), NodeID<Type>>,
+    /// HashMap from `inner_type` to `DynamicArray { inner_type }`
+    pub dynamic_array_types: HashMap<NodeID<Type>, NodeID<Type>>,
     /// HashMap from `pointed_to` to `Pointer { pointed_to }`
     pub pointer_types: HashMap<NodeID<Type>, NodeID<Type>>,
     /// HashMap from `pointed_to` to `MultiPointer { pointed_to }`
     pub multipointer_types: HashMap<NodeID<Type>, NodeID<Type>>,
     /// HashMap from `parameters` to HashMap from `return_type` to `Procedure { parameters, return_type }`
     pub procedure_types: HashMap<Vec<NodeID<Type>>, HashMap<NodeID<Type>, NodeID<Type>>>,
+    /// HashMap from `(members, default_values)` to `Struct { members }`
+    pub struct_types: HashMap<(Vec<StructMember>, Vec<Option<Value<'filepath>>>), NodeID<Type>>,
+    /// HashMap from the struct types whose members have default values to the default value of each member,
+    /// which are not part of `Type::Struct`
+    pub struct_default_values: HashMap<NodeID<Type>, Vec<Option<Value<'filepath>>>>,
+    /// HashMap from the sorted `variants` to `Union { variants }`
+    pub union_types: HashMap<Vec<NodeID<Type>>, NodeID<Type>>,
 }
 
-impl CommonTypes {
+impl<'filepath> CommonTypes<'filepath> {
     pub fn get_slice(&mut self, types: &mut Nodes<Type>, inner_type: NodeID<Type>) -> NodeID<Type> {
         if let Some(&slice) = self.slice_types.get(&inner_type) {
             slice
@@ -33,6 +57,37 @@ impl CommonTypes {
         }
     }
 
+    pub fn get_array(
+        &mut self,
+        types: &mut Nodes<Type>,
+        length: usize,
+        inner_type: NodeID<Type>,
+    ) -> NodeID<Type> {
+        if let Some(&array) = self.array_types.get(&(length, inner_type)) {
+            array
+        } else {
+            let array = types.insert(Type::Array { length, inner_type });
+            let previous = self.array_types.insert((length, inner_type), array);
+            assert!(previous.is_none());
+            array
+        }
+    }
+
+    pub fn get_dynamic_array(
+        &mut self,
+        types: &mut Nodes<Type>,
+        inner_type: NodeID<Type>,
+    ) -> NodeID<Type> {
+        if let Some(&dynamic_array) = self.dynamic_array_types.get(&inner_type) {
+            dynamic_array
+        } else {
+            let dynamic_array = types.insert(Type::DynamicArray { inner_type });
+            let previous = self.dynamic_array_types.insert(inner_type, dynamic_array);
+            assert!(previous.is_none());
+            dynamic_array
+        }
+    }
+
     pub fn get_pointer(
         &mut self,
         types: &mut Nodes<Type>,
@@ -86,4 +141,73 @@ impl CommonTypes {
             procedure
         }
     }
+
+    pub fn get_struct(
+        &mut self,
+        types: &mut Nodes<Type>,
+        members: &[StructMember],
+    ) -> NodeID<Type> {
+        self.get_struct_with_default_values(types, members, &[])
+    }
+
+    /// `default_values` is either empty or has a value for each member, struct types with the same members
+    /// but different default values are different types
+    pub fn get_struct_with_default_values(
+        &mut self,
+        types: &mut Nodes<Type>,
+        members: &[StructMember],
+        default_values: &[Option<Value<'filepath>>],
+    ) -> NodeID<Type> {
+        let key = (members.to_vec(), default_values.to_vec());
+        if let Some(&struct_type) = self.struct_types.get(&key) {
+            struct_type
+        } else {
+            let struct_type = types.insert(Type::Struct {
+                members: members.to_vec(),
+            });
+            if !default_values.is_empty() {
+                self.struct_default_values
+                    .insert(struct_type, default_values.to_vec());
+            }
+            let previous = self.struct_types.insert(key, struct_type);
+            assert!(previous.is_none());
+            struct_type
+        }
+    }
+
+    /// Nested unions are flattened and duplicate variants removed, a union of a single type is just that type
+    /// and a union of no types is `never`
+    pub fn get_union(
+        &mut self,
+        types: &mut Nodes<Type>,
+        variants: &[NodeID<Type>],
+    ) -> NodeID<Type> {
+        let mut flattened = vec![];
+        for &variant in variants {
+            match types[variant] {
+                Type::Union { ref variants } => flattened.extend_from_slice(variants),
+                Type::Never => {}
+                _ => flattened.push(variant),
+            }
+        }
+        flattened.sort();
+        flattened.dedup();
+
+        match flattened[..] {
+            [] => self.never,
+            [variant] => variant,
+            _ => {
+                if let Some(&union) = self.union_types.get(&flattened) {
+                    union
+                } else {
+                    let union = types.insert(Type::Union {
+                        variants: flattened.clone(),
+                    });
+                    let previous = self.union_types.insert(flattened, union);
+                    assert!(previous.is_none());
+                    union
+                }
+            }
+        }
+    }
 }
@@ -1,15 +1,37 @@
 use crate::nodes::{NodeID, Nodes};
+use std::ops::RangeInclusive;
 
 #[derive(Debug, Clone)]
 pub enum Type {
     Type,
     Void,
+    Never,
+    Label,
+    Bool,
+    /// An integer with the pointer width of the target, see [`TARGET_POINTER_BITS`]
     Int,
+    /// An unsigned integer with the pointer width of the target, see [`TARGET_POINTER_BITS`]
     UInt,
     U8,
+    U16,
+    U32,
+    U64,
+    S8,
+    S16,
+    S32,
+    S64,
+    /// A pointer to a value of any type, which has to be cast to a typed pointer to be used
+    RawPtr,
     Slice {
         inner_type: NodeID<Type>,
     },
+    Array {
+        length: usize,
+        inner_type: NodeID<Type>,
+    },
+    DynamicArray {
+        inner_type: NodeID<Type>,
+    },
     Pointer {
         pointed_to: NodeID<Type>,
     },
@@ -20,17 +42,113 @@ pub enum Type {
         parameters: Vec<NodeID<Type>>,
         return_type: NodeID<Type>,
     },
+    Struct {
+        members: Vec<StructMember>,
+    },
+    /// The variants are sorted and never contain another union, so that the same union is always the same type
+    Union {
+        variants: Vec<NodeID<Type>>,
+    },
+    /// The type of the builtin procedures, which are checked by the binder instead of having a procedure type
+    Builtin,
+    /// The type of the procedures with compile time parameters, which only get a procedure type once
+    /// the compile time arguments are known
+    GenericProcedure,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StructMember {
+    /// `None` for the members of struct values like `(a, b)` that were not given a struct type
+    pub name: Option<String>,
+    pub typ: NodeID<Type>,
 }
 
+/// The number of bits in a pointer on the target, which is also the size of `int` and `uint`
+pub const TARGET_POINTER_BITS: u32 = usize::BITS;
+
 impl Type {
+    /// Whether the integer type is signed and how many bits it has, if this is an integer type
+    pub fn integer_format(&self) -> Option<(bool, u32)> {
+        Some(match *self {
+            Type::Int => (true, TARGET_POINTER_BITS),
+            Type::UInt => (false, TARGET_POINTER_BITS),
+            Type::U8 => (false, 8),
+            Type::U16 => (false, 16),
+            Type::U32 => (false, 32),
+            Type::U64 => (false, 64),
+            Type::S8 => (true, 8),
+            Type::S16 => (true, 16),
+            Type::S32 => (true, 32),
+            Type::S64 => (true, 64),
+            _ => return None,
+        })
+    }
+
+    /// The range of values that can be stored in this type, if it is an integer type
+    pub fn integer_range(&self) -> Option<RangeInclusive<i128>> {
+        let (signed, bits) = self.integer_format()?;
+        Some(if signed {
+            -(1 << (bits - 1))..=(1 << (bits - 1)) - 1
+        } else {
+            0..=(1 << bits) - 1
+        })
+    }
+
+    /// Wraps the value around into the range of this integer type by keeping only its lowest bits,
+    /// which is how the integer casts that do not fit are done
+    pub fn wrap_integer(&self, value: i128) -> Option<i128> {
+        let (signed, bits) = self.integer_format()?;
+        let truncated = value & ((1 << bits) - 1);
+        Some(if signed && truncated >= 1 << (bits - 1) {
+            truncated - (1 << bits)
+        } else {
+            truncated
+        })
+    }
+
+    /// The number of members, if this is a struct or an array
+    pub fn member_count(&self) -> Option<usize> {
+        match *self {
+            Type::Struct { ref members } => Some(members.len()),
+            Type::Array { length, .. } => Some(length),
+            _ => None,
+        }
+    }
+
+    /// The type of the member at `index`, if this is a struct or an array that has that member
+    pub fn member_type(&self, index: usize) -> Option<NodeID<Type>> {
+        match *self {
+            Type::Struct { ref members } => Some(members.get(index)?.typ),
+            Type::Array { length, inner_type } => (index < length).then_some(inner_type),
+            _ => None,
+        }
+    }
+
     pub fn pretty_print(&self, types: &Nodes<Type>) -> String {
         match *self {
             Type::Type => "type".to_string(),
             Type::Void => "void".to_string(),
+            Type::Never => "never".to_string(),
+            Type::Label => "label".to_string(),
+            Type::Bool => "bool".to_string(),
             Type::Int => "int".to_string(),
             Type::UInt => "uint".to_string(),
             Type::U8 => "u8".to_string(),
+            Type::U16 => "u16".to_string(),
+            Type::U32 => "u32".to_string(),
+            Type::U64 => "u64".to_string(),
+            Type::S8 => "s8".to_string(),
+            Type::S16 => "s16".to_string(),
+            Type::S32 => "s32".to_string(),
+            Type::S64 => "s64".to_string(),
+            Type::RawPtr => "rawptr".to_string(),
             Type::Slice { inner_type } => format!("[]{}", types[inner_type].pretty_print(types)),
+            Type::Array { length, inner_type } => {
+                format!("[{length}]{}", types[inner_type].pretty_print(types))
+            }
+            Type::DynamicArray { inner_type } => {
+                format!("[..]{}", types[inner_type].pretty_print(types))
+            }
             Type::Pointer { pointed_to } => format!("^{}", types[pointed_to].pretty_print(types)),
             Type::Multipointer { pointed_to } => {
                 format!("[^]{}", types[pointed_to].pretty_print(types))
@@ -50,6 +168,32 @@ impl Type {
                 result += &types[return_type].pretty_print(types);
                 result
             }
+            Type::Struct { ref members } => {
+                let mut result = "(".to_string();
+                for (i, member) in members.iter().enumerate() {
+                    if i > 0 {
+                        result += ", ";
+                    }
+                    if let Some(ref name) = member.name {
+                        result += name;
+                        result += ": ";
+                    }
+                    result += &types[member.typ].pretty_print(types);
+                }
+                // a struct with a single member needs a trailing comma to not be a parenthesised expression
+                if members.len() == 1 {
+                    result += ",";
+                }
+                result += ")";
+                result
+            }
+            Type::Union { ref variants } => variants
+                .iter()
+                .map(|&variant| types[variant].pretty_print(types))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            Type::Builtin => "builtin".to_string(),
+            Type::GenericProcedure => "generic procedure".to_string(),
         }
     }
 }
@@ -0,0 +1,334 @@
+use crate::{
+    binding::BindingError,
+    eval::EvalError,
+    parsing::ParsingError,
+    tokens::{GetLocation, LexerError, SourceLocation},
+};
+use std::io::Write;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// Spans with more lines than this only show their first and last line
+const MAX_SPAN_LINES: usize = 3;
+
+/// An error with the span of source code that caused it, which can be rendered with the source lines it points at
+#[derive(Debug, Clone)]
+pub struct Diagnostic<'filepath> {
+    pub message: String,
+    pub location: SourceLocation<'filepath>,
+    pub end_location: SourceLocation<'filepath>,
+    pub labels: Vec<DiagnosticLabel<'filepath>>,
+    pub notes: Vec<String>,
+}
+
+/// A secondary span that explains a diagnostic, like where something was declared
+#[derive(Debug, Clone)]
+pub struct DiagnosticLabel<'filepath> {
+    pub location: SourceLocation<'filepath>,
+    pub end_location: SourceLocation<'filepath>,
+    pub message: String,
+}
+
+impl<'filepath> Diagnostic<'filepath> {
+    pub fn new(
+        message: String,
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    ) -> Self {
+        Self {
+            message,
+            location,
+            end_location,
+            labels: vec![],
+            notes: vec![],
+        }
+    }
+
+    pub fn with_label(
+        mut self,
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        message: String,
+    ) -> Self {
+        self.labels.push(DiagnosticLabel {
+            location,
+            end_location,
+            message,
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: String) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    /// Only the spans that are in `filepath` get their source lines printed,
+    /// the others (like builtins) are only referred to by their location
+    pub fn render(
+        &self,
+        filepath: &str,
+        source: &str,
+        colored: bool,
+        f: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        let paint = |style: &str, text: &str| {
+            if colored {
+                format!("{style}{text}{RESET}")
+            } else {
+                text.to_string()
+            }
+        };
+
+        let lines = source
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect::<Vec<_>>();
+        let gutter_width = std::iter::once(self.end_location)
+            .chain(self.labels.iter().map(|label| label.end_location))
+            .map(|location| location.line.get().to_string().len())
+            .max()
+            .unwrap();
+        let gutter = " ".repeat(gutter_width);
+
+        writeln!(
+            f,
+            "{}{}",
+            paint(RED, "error"),
+            paint(BOLD, &format!(": {}", self.message)),
+        )?;
+        writeln!(f, "{gutter}{} {}", paint(BLUE, "-->"), self.location)?;
+
+        let spans = std::iter::once((self.location, self.end_location, '^', RED, "")).chain(
+            self.labels.iter().map(|label| {
+                (
+                    label.location,
+                    label.end_location,
+                    '-',
+                    BLUE,
+                    label.message.as_str(),
+                )
+            }),
+        );
+        for (location, end_location, marker, style, message) in spans {
+            if location.filepath != filepath {
+                writeln!(f, "{gutter}{} {location}: {message}", paint(BLUE, ":::"))?;
+                continue;
+            }
+
+            writeln!(f, "{gutter} {}", paint(BLUE, "|"))?;
+
+            // a span that ends with a newline ends at the start of the next line, which has nothing to underline
+            let last_line = if end_location.line > location.line && end_location.column.get() == 1 {
+                end_location.line.get() - 1
+            } else {
+                end_location.line.get()
+            };
+            let first_line = location.line.get();
+            for line_number in first_line..=last_line {
+                if last_line - first_line + 1 > MAX_SPAN_LINES
+                    && line_number != first_line
+                    && line_number != last_line
+                {
+                    if line_number == first_line + 1 {
+                        writeln!(f, "{}", paint(BLUE, "..."))?;
+                    }
+                    continue;
+                }
+
+                let line = lines.get(line_number - 1).copied().unwrap_or("");
+                writeln!(
+                    f,
+                    "{} {line}",
+                    paint(BLUE, &format!("{line_number:>gutter_width$} |")),
+                )?;
+
+                let line_length = line.chars().count();
+                let start_column = if line_number == first_line {
+                    location.column.get()
+                } else {
+                    line.chars().take_while(|c| c.is_whitespace()).count() + 1
+                };
+                let end_column = if line_number == end_location.line.get() {
+                    end_location.column.get()
+                } else {
+                    line_length + 1
+                };
+                // tabs are kept so that the underline lines up with the source line
+                let padding = line
+                    .chars()
+                    .chain(std::iter::repeat(' '))
+                    .take(start_column - 1)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect::<String>();
+                let underline = marker
+                    .to_string()
+                    .repeat(end_column.saturating_sub(start_column).max(1));
+                let message = if line_number == last_line && !message.is_empty() {
+                    format!(" {message}")
+                } else {
+                    String::new()
+                };
+                writeln!(
+                    f,
+                    "{gutter} {} {padding}{}",
+                    paint(BLUE, "|"),
+                    paint(style, &format!("{underline}{message}")),
+                )?;
+            }
+        }
+
+        for note in &self.notes {
+            writeln!(f, "{gutter} {} {note}", paint(BOLD, "= note:"))?;
+        }
+        writeln!(f)
+    }
+}
+
+impl<'filepath> From<LexerError<'filepath>> for Diagnostic<'filepath> {
+    fn from(error: LexerError<'filepath>) -> Self {
+        Diagnostic::new(
+            error.to_string(),
+            error.get_location(),
+            error.get_end_location(),
+        )
+    }
+}
+
+impl<'filepath, 'source> From<ParsingError<'filepath, 'source>> for Diagnostic<'filepath> {
+    fn from(error: ParsingError<'filepath, 'source>) -> Self {
+        match error {
+            ParsingError::LexerError(error) => error.into(),
+            error => Diagnostic::new(
+                error.to_string(),
+                error.get_location(),
+                error.get_end_location(),
+            ),
+        }
+    }
+}
+
+impl<'filepath> From<BindingError<'filepath>> for Diagnostic<'filepath> {
+    fn from(error: BindingError<'filepath>) -> Self {
+        let diagnostic = Diagnostic::new(
+            error.to_string(),
+            error.get_location(),
+            error.get_end_location(),
+        );
+        match error {
+            BindingError::EvalError(error) => error.into(),
+            BindingError::CompileTimeCallFailed { ref error, .. } => diagnostic.with_label(
+                error.get_location(),
+                error.get_end_location(),
+                "This is where it fails".to_string(),
+            ),
+            BindingError::OnlyConstantsInGlobalScope { .. } => {
+                diagnostic.with_note("Constants are declared with `name :: value`".to_string())
+            }
+            BindingError::IntegerLiteralOutOfRange {
+                ref typ, ref range, ..
+            } => diagnostic.with_note(format!(
+                "The type {typ} can hold integers from {} to {}",
+                range.start(),
+                range.end(),
+            )),
+            BindingError::ExpectedConstant {
+                declaration: Some((location, end_location)),
+                ..
+            } => diagnostic.with_label(
+                location,
+                end_location,
+                "The variable is declared here".to_string(),
+            ),
+            BindingError::ProcedureBodyTypeMismatch {
+                return_type_location,
+                return_type_end_location,
+                ref return_type,
+                ..
+            } => diagnostic.with_label(
+                return_type_location,
+                return_type_end_location,
+                format!("The return type is declared as {return_type} here"),
+            ),
+            BindingError::MismatchedBranchTypes {
+                then_location,
+                then_end_location,
+                else_location,
+                else_end_location,
+                ref then_type,
+                ref else_type,
+                ..
+            } => diagnostic
+                .with_label(
+                    then_location,
+                    then_end_location,
+                    format!("This has type {then_type}"),
+                )
+                .with_label(
+                    else_location,
+                    else_end_location,
+                    format!("This has type {else_type}"),
+                ),
+            BindingError::LabelUsedAsValue {
+                label_location,
+                label_end_location,
+                ..
+            } => diagnostic.with_label(
+                label_location,
+                label_end_location,
+                "The label is declared here".to_string(),
+            ),
+            BindingError::DuplicateMemberName {
+                previous_location,
+                previous_end_location,
+                ..
+            } => diagnostic.with_label(
+                previous_location,
+                previous_end_location,
+                "The first member with this name is declared here".to_string(),
+            ),
+            BindingError::DuplicateArgument {
+                previous_location,
+                previous_end_location,
+                ..
+            } => diagnostic.with_label(
+                previous_location,
+                previous_end_location,
+                "The first argument for it is here".to_string(),
+            ),
+            BindingError::TypeMismatch {
+                is_rawptr: true, ..
+            }
+            | BindingError::NotAPointer {
+                is_rawptr: true, ..
+            } => {
+                diagnostic.with_note(
+                    "A rawptr has to be cast to a typed pointer before it is used, like `^int(pointer)`"
+                        .to_string(),
+                )
+            }
+            BindingError::RecursiveConstant { .. } => diagnostic.with_note(
+                "A procedure can only call itself if the types of its parameters and its return type are written out"
+                    .to_string(),
+            ),
+            BindingError::UnknownLabel { .. } => diagnostic.with_note(
+                "Only labels declared in the same or an enclosing block can be jumped to"
+                    .to_string(),
+            ),
+            _ => diagnostic,
+        }
+    }
+}
+
+impl<'filepath> From<EvalError<'filepath>> for Diagnostic<'filepath> {
+    fn from(error: EvalError<'filepath>) -> Self {
+        Diagnostic::new(
+            error.to_string(),
+            error.get_location(),
+            error.get_end_location(),
+        )
+    }
+}
@@ -1,60 +1,1090 @@
+mod memory;
 mod values;
 
+pub use memory::*;
 pub use values::*;
 
 use crate::{
-    binding::BoundNode,
+    binding::{BinaryOperator, BoundNode, Builtin, CommonTypes, Type, UnaryOperator},
     nodes::{NodeID, Nodes},
+    tokens::{GetLocation, SourceLocation},
 };
+use derive_more::Display;
+use std::collections::HashMap;
 
 pub fn eval_bound_node<'filepath>(
     node: NodeID<BoundNode<'filepath>>,
-    nodes: &mut Nodes<BoundNode<'filepath>>,
-) -> Value<'filepath> {
-    match nodes[node] {
-        BoundNode::Block {
-            ref expressions, ..
-        } => {
-            _ = expressions;
-            todo!()
-        }
-        BoundNode::Constant { ref value, .. } => value.clone(),
-        BoundNode::Declaration { typ, value, .. } => {
-            _ = typ;
-            _ = value;
-            todo!()
-        }
-        BoundNode::Type { typ, .. } => Value::Type { typ },
-        BoundNode::Name {
-            referenced_node, ..
-        } => eval_bound_node(referenced_node, nodes),
-        BoundNode::MemberAccess {
-            operand,
-            member_index,
-            ..
-        } => {
-            _ = operand;
-            _ = member_index;
-            todo!()
-        }
-        BoundNode::Call {
-            operand,
-            ref arguments,
-            ..
-        } => {
-            _ = operand;
-            _ = arguments;
-            todo!();
-        }
-        BoundNode::Cast {
-            to_type,
-            ref from_expressions,
+    nodes: &Nodes<BoundNode<'filepath>>,
+    types: &Nodes<Type>,
+    common_types: &CommonTypes<'filepath>,
+) -> Result<Value<'filepath>, EvalError<'filepath>> {
+    Interpreter::new(nodes, types, common_types).eval(node)
+}
+
+pub struct Interpreter<'a, 'filepath> {
+    nodes: &'a Nodes<BoundNode<'filepath>>,
+    types: &'a Nodes<Type>,
+    common_types: &'a CommonTypes<'filepath>,
+    memory: Memory<'filepath>,
+    /// Each frame maps the declarations of a procedure call to the allocation that holds their value
+    frames: Vec<HashMap<NodeID<BoundNode<'filepath>>, usize>>,
+}
+
+impl<'a, 'filepath> Interpreter<'a, 'filepath> {
+    pub fn new(
+        nodes: &'a Nodes<BoundNode<'filepath>>,
+        types: &'a Nodes<Type>,
+        common_types: &'a CommonTypes<'filepath>,
+    ) -> Self {
+        Self {
+            nodes,
+            types,
+            common_types,
+            memory: Memory::new(),
+            frames: vec![HashMap::new()],
+        }
+    }
+
+    pub fn allocate(&mut self, values: Vec<Value<'filepath>>) -> Address {
+        Address {
+            allocation: self.memory.allocate(values),
+            index: 0,
+            members: vec![],
+        }
+    }
+
+    pub fn call(
+        &mut self,
+        procedure: NodeID<BoundNode<'filepath>>,
+        arguments: Vec<Value<'filepath>>,
+    ) -> Result<Value<'filepath>, EvalError<'filepath>> {
+        let BoundNode::Procedure {
+            ref parameters,
+            body,
             ..
-        } => {
-            _ = to_type;
-            _ = from_expressions;
-            todo!()
+        } = self.nodes[procedure]
+        else {
+            unreachable!()
+        };
+
+        self.frames.push(HashMap::new());
+        for (&parameter, argument) in parameters.iter().zip(arguments) {
+            self.declare(parameter, argument);
+        }
+        let result = self.eval(body);
+        for (_, allocation) in self.frames.pop().unwrap() {
+            self.memory.free(allocation);
+        }
+        result
+    }
+
+    pub fn eval(
+        &mut self,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Value<'filepath>, EvalError<'filepath>> {
+        match self.eval_node(node) {
+            Ok(value) => Ok(value),
+            Err(Unwind::Error(error)) => Err(error),
+            Err(Unwind::Goto { .. }) => {
+                unreachable!("the binder only allows jumps to labels in enclosing blocks")
+            }
+        }
+    }
+
+    fn eval_node(
+        &mut self,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Value<'filepath>, Unwind<'filepath>> {
+        Ok(match self.nodes[node] {
+            BoundNode::Block {
+                ref expressions, ..
+            } => {
+                let mut result = Value::Void;
+                let mut index = 0;
+                while let Some(&expression) = expressions.get(index) {
+                    index += 1;
+                    result = match self.eval_node(expression) {
+                        Ok(value) => value,
+                        Err(Unwind::Goto { label }) => {
+                            // keep unwinding if the label is not in this block
+                            let Some(position) = expressions.iter().position(|&e| e == label)
+                            else {
+                                return Err(Unwind::Goto { label });
+                            };
+                            index = position + 1;
+                            Value::Void
+                        }
+                        Err(error) => return Err(error),
+                    };
+                }
+                result
+            }
+            BoundNode::Constant { ref value, .. } => value.clone(),
+            BoundNode::Declaration { typ, value, .. } => {
+                let value = if let Some(value) = value {
+                    self.eval_node(value)?
+                } else {
+                    self.default_value(typ)
+                };
+                self.declare(node, value.clone());
+                value
+            }
+            BoundNode::Integer { value, .. } => Value::Integer {
+                value: value as i128,
+            },
+            BoundNode::Type { typ, .. } => Value::Type { typ },
+            BoundNode::Name {
+                referenced_node, ..
+            } => {
+                if let BoundNode::Declaration { .. } = self.nodes[referenced_node] {
+                    let address = self.declaration_address(referenced_node, node)?;
+                    self.memory.read(&address).unwrap().clone()
+                } else {
+                    self.eval_node(referenced_node)?
+                }
+            }
+            BoundNode::Unary {
+                operator,
+                operand,
+                result_type,
+                ..
+            } => match (operator, self.eval_node(operand)?) {
+                (UnaryOperator::Identity, value) => value,
+                (UnaryOperator::Negate, Value::Integer { value }) => {
+                    self.integer(value.checked_neg(), result_type, node)?
+                }
+                (UnaryOperator::LogicalNot, Value::Bool { value }) => Value::Bool { value: !value },
+                _ => unreachable!(),
+            },
+            BoundNode::AddressOf { operand, .. } => Value::Pointer {
+                address: Some(self.place_address(operand)?),
+            },
+            BoundNode::Dereference { .. } => {
+                let address = self.place_address(node)?;
+                self.read(&address, node)?
+            }
+            BoundNode::Binary {
+                left,
+                operator,
+                right,
+                result_type,
+                ..
+            } => match operator {
+                BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => {
+                    let Value::Bool { value: left } = self.eval_node(left)? else {
+                        unreachable!()
+                    };
+                    // the right side is only evaluated if it can change the result
+                    if left == (operator == BinaryOperator::LogicalOr) {
+                        Value::Bool { value: left }
+                    } else {
+                        self.eval_node(right)?
+                    }
+                }
+                BinaryOperator::Equal => Value::Bool {
+                    value: self.eval_node(left)? == self.eval_node(right)?,
+                },
+                BinaryOperator::NotEqual => Value::Bool {
+                    value: self.eval_node(left)? != self.eval_node(right)?,
+                },
+                BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Remainder => {
+                    let (left, right) = (self.eval_node(left)?, self.eval_node(right)?);
+                    self.arithmetic(operator, left, right, result_type, node)?
+                }
+                BinaryOperator::LessThan
+                | BinaryOperator::LessThanEqual
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanEqual => {
+                    let (Value::Integer { value: left }, Value::Integer { value: right }) =
+                        (self.eval_node(left)?, self.eval_node(right)?)
+                    else {
+                        unreachable!()
+                    };
+                    Value::Bool {
+                        value: match operator {
+                            BinaryOperator::LessThan => left < right,
+                            BinaryOperator::LessThanEqual => left <= right,
+                            BinaryOperator::GreaterThan => left > right,
+                            BinaryOperator::GreaterThanEqual => left >= right,
+                            _ => unreachable!(),
+                        },
+                    }
+                }
+            },
+            BoundNode::Assignment {
+                place,
+                operator,
+                value,
+                ..
+            } => {
+                let address = self.place_address(place)?;
+                let value = self.eval_node(value)?;
+                let value = if let Some(operator) = operator {
+                    let current = self.read(&address, node)?;
+                    let place_type = self.nodes[place].get_type(self.nodes);
+                    self.arithmetic(operator, current, value, place_type, node)?
+                } else {
+                    value
+                };
+                self.write(&address, value, node)?;
+                Value::Void
+            }
+            BoundNode::If {
+                condition,
+                then_expression,
+                else_expression,
+                ..
+            } => {
+                let Value::Bool { value: condition } = self.eval_node(condition)? else {
+                    unreachable!()
+                };
+                if condition {
+                    let value = self.eval_node(then_expression)?;
+                    if else_expression.is_some() {
+                        value
+                    } else {
+                        Value::Void
+                    }
+                } else if let Some(else_expression) = else_expression {
+                    self.eval_node(else_expression)?
+                } else {
+                    Value::Void
+                }
+            }
+            BoundNode::While {
+                condition, body, ..
+            } => {
+                while let Value::Bool { value: true } = self.eval_node(condition)? {
+                    self.eval_node(body)?;
+                }
+                Value::Void
+            }
+            BoundNode::MemberAccess {
+                operand,
+                member_index,
+                ..
+            } => match (self.eval_node(operand)?, member_index) {
+                (Value::Slice { data, .. }, 0) => Value::Pointer { address: data },
+                (Value::Slice { length, .. }, 1) => Value::Integer {
+                    value: length as i128,
+                },
+                (Value::DynamicArray { data, .. }, 0) => Value::Pointer { address: data },
+                (Value::DynamicArray { length, .. }, 1) => Value::Integer {
+                    value: length as i128,
+                },
+                (Value::DynamicArray { capacity, .. }, 2) => Value::Integer {
+                    value: capacity as i128,
+                },
+                (Value::Struct { mut members }, member_index) => members.swap_remove(member_index),
+                (Value::Array { mut elements }, member_index) => elements.swap_remove(member_index),
+                _ => unreachable!(),
+            },
+            BoundNode::StructIndex {
+                operand,
+                index,
+                result_type,
+                ..
+            } => {
+                let Value::Struct { mut members } = self.eval_node(operand)? else {
+                    unreachable!()
+                };
+                let Value::Integer { value: index } = self.eval_node(index)? else {
+                    unreachable!()
+                };
+                let member_index = self.check_index(index, members.len(), node)?;
+                // all the members have the same type if the result is not a union
+                match (&self.types[result_type], members.swap_remove(member_index)) {
+                    // unions are never nested, so a member that is a union already holds the variant
+                    (Type::Union { .. }, value @ Value::Union { .. }) => value,
+                    (Type::Union { .. }, value) => {
+                        let Type::Struct {
+                            members: ref member_types,
+                        } = self.types[self.nodes[operand].get_type(self.nodes)]
+                        else {
+                            unreachable!()
+                        };
+                        Value::Union {
+                            typ: member_types[member_index].typ,
+                            value: Box::new(value),
+                        }
+                    }
+                    (_, value) => value,
+                }
+            }
+            BoundNode::Index { operand, index, .. } => {
+                // only the elements of arrays are not always in memory
+                if !matches!(
+                    self.types[self.nodes[operand].get_type(self.nodes)],
+                    Type::Array { .. }
+                ) {
+                    let address = self.place_address(node)?;
+                    return Ok(self.read(&address, node)?);
+                }
+
+                let Value::Array { mut elements } = self.eval_node(operand)? else {
+                    unreachable!()
+                };
+                let Value::Integer { value: index } = self.eval_node(index)? else {
+                    unreachable!()
+                };
+                let element_index = self.check_index(index, elements.len(), node)?;
+                elements.swap_remove(element_index)
+            }
+            BoundNode::Slice {
+                location,
+                end_location,
+                operand,
+                low,
+                high,
+                ..
+            } => {
+                // multipointers do not know their length
+                let (data, length) = match self.types[self.nodes[operand].get_type(self.nodes)] {
+                    Type::Array { length, .. } => {
+                        let mut address = self.place_address(operand)?;
+                        address.members.push(0);
+                        (Some(address), Some(length))
+                    }
+                    _ => match self.eval_node(operand)? {
+                        Value::Slice { data, length }
+                        | Value::DynamicArray { data, length, .. } => (data, Some(length)),
+                        Value::Pointer { address } => (address, None),
+                        _ => unreachable!(),
+                    },
+                };
+
+                let low = match low {
+                    Some(low) => {
+                        let Value::Integer { value } = self.eval_node(low)? else {
+                            unreachable!()
+                        };
+                        value
+                    }
+                    None => 0,
+                };
+                let high = match high {
+                    Some(high) => {
+                        let Value::Integer { value } = self.eval_node(high)? else {
+                            unreachable!()
+                        };
+                        value
+                    }
+                    // the binder makes sure that the end is given for a multipointer
+                    None => length.unwrap() as i128,
+                };
+
+                if let Some(length) = length {
+                    if high > length as i128 {
+                        return Err(EvalError::IndexOutOfBounds {
+                            location,
+                            end_location,
+                            index: high,
+                            length,
+                        }
+                        .into());
+                    }
+                }
+                if low < 0 || low > high {
+                    return Err(EvalError::InvalidSliceRange {
+                        location,
+                        end_location,
+                        low,
+                        high,
+                    }
+                    .into());
+                }
+
+                let data = match data {
+                    Some(data) => Some(self.offset(data, low, node)?),
+                    // an empty slice or dynamic array might not have any data, but a null multipointer
+                    // cannot be sliced into a slice that has elements
+                    None if length.is_none() && high > low => {
+                        return Err(EvalError::NullDereference {
+                            location,
+                            end_location,
+                        }
+                        .into());
+                    }
+                    None => None,
+                };
+                Value::Slice {
+                    data,
+                    length: (high - low) as usize,
+                }
+            }
+            BoundNode::UnionExtract {
+                location,
+                end_location,
+                operand,
+                result_type,
+            } => {
+                let Value::Union { typ, value } = self.eval_node(operand)? else {
+                    unreachable!()
+                };
+                match self.types[result_type] {
+                    Type::Union { ref variants } if variants.contains(&typ) => {
+                        Value::Union { typ, value }
+                    }
+                    _ if typ == result_type => *value,
+                    _ => {
+                        return Err(EvalError::WrongUnionVariant {
+                            location,
+                            end_location,
+                            expected: self.types[result_type].pretty_print(self.types),
+                            got: self.types[typ].pretty_print(self.types),
+                        }
+                        .into())
+                    }
+                }
+            }
+            BoundNode::Call {
+                location,
+                end_location,
+                operand,
+                ref arguments,
+                ..
+            } => {
+                let procedure = match self.eval_node(operand)? {
+                    Value::Procedure { procedure } => procedure,
+                    Value::Builtin { builtin } => {
+                        return self.call_builtin(builtin, arguments, node)
+                    }
+                    _ => unreachable!(),
+                };
+                let arguments = arguments
+                    .iter()
+                    .map(|&argument| self.eval_node(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let Some(procedure) = procedure else {
+                    return Err(EvalError::NullProcedureCall {
+                        location,
+                        end_location,
+                    }
+                    .into());
+                };
+                self.call(procedure, arguments)?
+            }
+            BoundNode::Cast {
+                to_type,
+                ref from_expressions,
+                ..
+            } => match from_expressions[..] {
+                _ if matches!(self.types[to_type], Type::Struct { .. })
+                    && !from_expressions.is_empty() =>
+                {
+                    Value::Struct {
+                        members: from_expressions
+                            .iter()
+                            .map(|&member| self.eval_node(member))
+                            .collect::<Result<_, _>>()?,
+                    }
+                }
+                _ if matches!(self.types[to_type], Type::Array { .. })
+                    && !from_expressions.is_empty() =>
+                {
+                    Value::Array {
+                        elements: from_expressions
+                            .iter()
+                            .map(|&element| self.eval_node(element))
+                            .collect::<Result<_, _>>()?,
+                    }
+                }
+                [] => self.default_value(to_type),
+                [from_expression] => {
+                    let value = self.eval_node(from_expression)?;
+                    match (&self.types[to_type], value) {
+                        (Type::Union { .. }, value @ Value::Union { .. }) => value,
+                        (Type::Union { .. }, value) => Value::Union {
+                            typ: self.nodes[from_expression].get_type(self.nodes),
+                            value: Box::new(value),
+                        },
+                        (Type::Bool, Value::Integer { value }) => Value::Bool { value: value != 0 },
+                        (to, Value::Bool { value }) if to.integer_range().is_some() => {
+                            Value::Integer {
+                                value: value.into(),
+                            }
+                        }
+                        (to, Value::Integer { value }) if to.integer_range().is_some() => {
+                            Value::Integer {
+                                value: to.wrap_integer(value).unwrap(),
+                            }
+                        }
+                        (Type::Slice { .. }, Value::DynamicArray { data, length, .. }) => {
+                            Value::Slice { data, length }
+                        }
+                        (
+                            Type::Pointer { .. } | Type::Multipointer { .. } | Type::RawPtr,
+                            value @ Value::Pointer { .. },
+                        ) => value,
+                        _ => unreachable!(),
+                    }
+                }
+                _ => unreachable!(),
+            },
+            BoundNode::Procedure { .. } => Value::Procedure {
+                procedure: Some(node),
+            },
+            BoundNode::GenericProcedure { .. } => Value::GenericProcedure { procedure: node },
+            BoundNode::Label { .. } => Value::Void,
+            BoundNode::Goto { label, .. } => return Err(Unwind::Goto { label }),
+            BoundNode::UnboundConstant { .. } => {
+                unreachable!("constants are bound before the program is run")
+            }
+            BoundNode::Error { .. } => unreachable!("programs that failed to parse are not run"),
+        })
+    }
+
+    /// Gets the address of a node that the binder has checked to be a place
+    fn place_address(
+        &mut self,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Address, Unwind<'filepath>> {
+        Ok(match self.nodes[node] {
+            BoundNode::Name {
+                referenced_node, ..
+            } => self.declaration_address(referenced_node, node)?,
+            BoundNode::Dereference {
+                location,
+                end_location,
+                operand,
+                ..
+            } => {
+                let Value::Pointer { address } = self.eval_node(operand)? else {
+                    unreachable!()
+                };
+                address.ok_or(EvalError::NullDereference {
+                    location,
+                    end_location,
+                })?
+            }
+            BoundNode::MemberAccess {
+                operand,
+                member_index,
+                ..
+            } => {
+                let mut address = self.place_address(operand)?;
+                address.members.push(member_index);
+                address
+            }
+            BoundNode::Index {
+                location,
+                end_location,
+                operand,
+                index,
+                ..
+            } => match self.types[self.nodes[operand].get_type(self.nodes)] {
+                Type::Slice { .. } | Type::DynamicArray { .. } => {
+                    let (Value::Slice { data, length } | Value::DynamicArray { data, length, .. }) =
+                        self.eval_node(operand)?
+                    else {
+                        unreachable!()
+                    };
+                    let Value::Integer { value: index } = self.eval_node(index)? else {
+                        unreachable!()
+                    };
+                    let element_index = self.check_index(index, length, node)?;
+                    let data = data.ok_or(EvalError::NullDereference {
+                        location,
+                        end_location,
+                    })?;
+                    self.offset(data, element_index as i128, node)?
+                }
+                Type::Multipointer { .. } => {
+                    let Value::Pointer { address } = self.eval_node(operand)? else {
+                        unreachable!()
+                    };
+                    let Value::Integer { value: index } = self.eval_node(index)? else {
+                        unreachable!()
+                    };
+                    let address = address.ok_or(EvalError::NullDereference {
+                        location,
+                        end_location,
+                    })?;
+                    self.offset(address, index, node)?
+                }
+                Type::Array { length, .. } => {
+                    let mut address = self.place_address(operand)?;
+                    let Value::Integer { value: index } = self.eval_node(index)? else {
+                        unreachable!()
+                    };
+                    address.members.push(self.check_index(index, length, node)?);
+                    address
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!("the binder only allows places"),
+        })
+    }
+
+    /// Moves `address` by `offset` values, which only stays valid between the values of an allocation
+    /// or between the elements of an array
+    fn offset(
+        &self,
+        mut address: Address,
+        offset: i128,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Address, EvalError<'filepath>> {
+        let invalid_address = || EvalError::InvalidAddress {
+            location: self.nodes[node].get_location(),
+            end_location: self.nodes[node].get_end_location(),
+        };
+
+        if let Some((_, parent_members)) = address.members.split_last() {
+            // moving from one struct member to another would change the type of the value
+            let parent = Address {
+                allocation: address.allocation,
+                index: address.index,
+                members: parent_members.to_vec(),
+            };
+            if !matches!(self.memory.read(&parent), Some(Value::Array { .. })) {
+                return Err(invalid_address());
+            }
+        }
+        let index = address.members.last_mut().unwrap_or(&mut address.index);
+        *index = usize::try_from(*index as i128 + offset).map_err(|_| invalid_address())?;
+        Ok(address)
+    }
+
+    /// Checks that `index` is in bounds for a length of `length`
+    fn check_index(
+        &self,
+        index: i128,
+        length: usize,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<usize, EvalError<'filepath>> {
+        usize::try_from(index)
+            .ok()
+            .filter(|&index| index < length)
+            .ok_or_else(|| EvalError::IndexOutOfBounds {
+                location: self.nodes[node].get_location(),
+                end_location: self.nodes[node].get_end_location(),
+                index,
+                length,
+            })
+    }
+
+    /// Calls a builtin procedure, `arguments[0]` is always a place that holds the dynamic array
+    fn call_builtin(
+        &mut self,
+        builtin: Builtin,
+        arguments: &[NodeID<BoundNode<'filepath>>],
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Value<'filepath>, Unwind<'filepath>> {
+        let values = arguments[1..]
+            .iter()
+            .map(|&argument| self.eval_node(argument))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Type::DynamicArray { inner_type } =
+            self.types[self.nodes[arguments[0]].get_type(self.nodes)]
+        else {
+            unreachable!()
+        };
+        let array_address = self.place_address(arguments[0])?;
+        let Value::DynamicArray {
+            mut data,
+            mut length,
+            mut capacity,
+        } = self.read(&array_address, arguments[0])?
+        else {
+            unreachable!()
+        };
+
+        let result = match builtin {
+            Builtin::Append => {
+                let new_length = length + values.len();
+                if new_length > capacity {
+                    // the capacity is doubled so that appending one value at a time does not reallocate every time
+                    capacity = new_length.max(capacity * 2).max(8);
+                    data = Some(self.reallocate(data, length, capacity, node)?);
+                }
+                for (i, value) in values.into_iter().enumerate() {
+                    let mut address = data.clone().unwrap();
+                    address.index += length + i;
+                    self.write(&address, value, node)?;
+                }
+                length = new_length;
+                Value::Void
+            }
+            Builtin::Reserve | Builtin::Resize => {
+                let Value::Integer { value } = values[0] else {
+                    unreachable!()
+                };
+                let new_length = usize::try_from(value).unwrap();
+                if new_length > capacity {
+                    capacity = new_length;
+                    data = Some(self.reallocate(data, length, capacity, node)?);
+                }
+                if builtin == Builtin::Resize {
+                    for index in length..new_length {
+                        let mut address = data.clone().unwrap();
+                        address.index += index;
+                        self.write(&address, self.default_value(inner_type), node)?;
+                    }
+                    length = new_length;
+                }
+                Value::Void
+            }
+            Builtin::Pop => {
+                if length == 0 {
+                    return Err(EvalError::PopFromEmptyDynamicArray {
+                        location: self.nodes[node].get_location(),
+                        end_location: self.nodes[node].get_end_location(),
+                    }
+                    .into());
+                }
+                length -= 1;
+                let mut address = data.clone().unwrap();
+                address.index += length;
+                self.read(&address, node)?
+            }
+            Builtin::Free => {
+                if let Some(ref address) = data {
+                    self.memory.free(address.allocation);
+                }
+                data = None;
+                length = 0;
+                capacity = 0;
+                Value::Void
+            }
+        };
+
+        self.write(
+            &array_address,
+            Value::DynamicArray {
+                data,
+                length,
+                capacity,
+            },
+            arguments[0],
+        )?;
+        Ok(result)
+    }
+
+    /// Moves the first `length` values at `data` into a new allocation of `capacity` values and frees the old one
+    fn reallocate(
+        &mut self,
+        data: Option<Address>,
+        length: usize,
+        capacity: usize,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Address, EvalError<'filepath>> {
+        let mut values = Vec::with_capacity(capacity);
+        if let Some(ref data) = data {
+            for index in 0..length {
+                values.push(self.read(
+                    &Address {
+                        allocation: data.allocation,
+                        index: data.index + index,
+                        members: vec![],
+                    },
+                    node,
+                )?);
+            }
+            self.memory.free(data.allocation);
+        }
+        // the values after `length` are written before they are read, so they do not need to be the default value
+        values.resize(capacity, Value::Void);
+        Ok(self.allocate(values))
+    }
+
+    /// Reads the value at `address`, which is an error if it is dangling
+    fn read(
+        &self,
+        address: &Address,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Value<'filepath>, EvalError<'filepath>> {
+        self.memory
+            .read(address)
+            .cloned()
+            .ok_or_else(|| EvalError::InvalidAddress {
+                location: self.nodes[node].get_location(),
+                end_location: self.nodes[node].get_end_location(),
+            })
+    }
+
+    /// Writes the value at `address`, which is an error if it is dangling
+    fn write(
+        &mut self,
+        address: &Address,
+        value: Value<'filepath>,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<(), EvalError<'filepath>> {
+        self.memory
+            .write(address, value)
+            .ok_or_else(|| EvalError::InvalidAddress {
+                location: self.nodes[node].get_location(),
+                end_location: self.nodes[node].get_end_location(),
+            })
+    }
+
+    /// A goto can jump over a declaration to a label that is still in its scope, so `node`
+    /// can use a declaration that has not been evaluated
+    fn declaration_address(
+        &self,
+        declaration: NodeID<BoundNode<'filepath>>,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Address, EvalError<'filepath>> {
+        let &allocation = self
+            .frames
+            .last()
+            .unwrap()
+            .get(&declaration)
+            .ok_or_else(|| EvalError::UninitializedVariable {
+                location: self.nodes[node].get_location(),
+                end_location: self.nodes[node].get_end_location(),
+            })?;
+        Ok(Address {
+            allocation,
+            index: 0,
+            members: vec![],
+        })
+    }
+
+    fn declare(&mut self, declaration: NodeID<BoundNode<'filepath>>, value: Value<'filepath>) {
+        let allocation = self.memory.allocate(vec![value]);
+        if let Some(previous) = self
+            .frames
+            .last_mut()
+            .unwrap()
+            .insert(declaration, allocation)
+        {
+            self.memory.free(previous);
+        }
+    }
+
+    /// Applies one of the arithmetic operators, which are also used by the compound assignments
+    fn arithmetic(
+        &self,
+        operator: BinaryOperator,
+        left: Value<'filepath>,
+        right: Value<'filepath>,
+        result_type: NodeID<Type>,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Value<'filepath>, EvalError<'filepath>> {
+        // offsets a multipointer by a number of elements
+        if let Type::Multipointer { .. } = self.types[result_type] {
+            let (Value::Pointer { address }, Value::Integer { value: offset }) = (left, right)
+            else {
+                unreachable!()
+            };
+            let offset = if operator == BinaryOperator::Subtract {
+                -offset
+            } else {
+                offset
+            };
+            // a null multipointer stays null
+            let address = match address {
+                Some(address) => Some(self.offset(address, offset, node)?),
+                None => None,
+            };
+            return Ok(Value::Pointer { address });
+        }
+
+        let (Value::Integer { value: left }, Value::Integer { value: right }) = (left, right)
+        else {
+            unreachable!()
+        };
+        if right == 0 && matches!(operator, BinaryOperator::Divide | BinaryOperator::Remainder) {
+            return Err(EvalError::DivisionByZero {
+                location: self.nodes[node].get_location(),
+                end_location: self.nodes[node].get_end_location(),
+            });
+        }
+        let value = match operator {
+            BinaryOperator::Add => left.checked_add(right),
+            BinaryOperator::Subtract => left.checked_sub(right),
+            BinaryOperator::Multiply => left.checked_mul(right),
+            BinaryOperator::Divide => left.checked_div(right),
+            BinaryOperator::Remainder => left.checked_rem(right),
+            _ => unreachable!(),
+        };
+        self.integer(value, result_type, node)
+    }
+
+    /// Checks that the result of the integer operation `node` fits in `typ`, `None` means the operation overflowed
+    fn integer(
+        &self,
+        value: Option<i128>,
+        typ: NodeID<Type>,
+        node: NodeID<BoundNode<'filepath>>,
+    ) -> Result<Value<'filepath>, EvalError<'filepath>> {
+        match value {
+            Some(value) if self.types[typ].integer_range().unwrap().contains(&value) => {
+                Ok(Value::Integer { value })
+            }
+            _ => Err(EvalError::IntegerOverflow {
+                location: self.nodes[node].get_location(),
+                end_location: self.nodes[node].get_end_location(),
+                typ: self.types[typ].pretty_print(self.types),
+            }),
+        }
+    }
+
+    fn default_value(&self, typ: NodeID<Type>) -> Value<'filepath> {
+        match self.types[typ] {
+            Type::Type => Value::Type {
+                typ: self.common_types.void,
+            },
+            Type::Void => Value::Void,
+            Type::Bool => Value::Bool { value: false },
+            Type::Int
+            | Type::UInt
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::S8
+            | Type::S16
+            | Type::S32
+            | Type::S64 => Value::Integer { value: 0 },
+            Type::Slice { .. } => Value::Slice {
+                data: None,
+                length: 0,
+            },
+            Type::Array { length, inner_type } => Value::Array {
+                elements: vec![self.default_value(inner_type); length],
+            },
+            Type::DynamicArray { .. } => Value::DynamicArray {
+                data: None,
+                length: 0,
+                capacity: 0,
+            },
+            Type::Pointer { .. } | Type::Multipointer { .. } | Type::RawPtr => {
+                Value::Pointer { address: None }
+            }
+            Type::Procedure { .. } => Value::Procedure { procedure: None },
+            Type::Struct { ref members } => {
+                let default_values = self.common_types.struct_default_values.get(&typ);
+                Value::Struct {
+                    members: members
+                        .iter()
+                        .enumerate()
+                        .map(|(i, member)| {
+                            default_values
+                                .and_then(|default_values| default_values[i].clone())
+                                .unwrap_or_else(|| self.default_value(member.typ))
+                        })
+                        .collect(),
+                }
+            }
+            // the binder does not allow a default value of these
+            Type::Never
+            | Type::Label
+            | Type::Union { .. }
+            | Type::Builtin
+            | Type::GenericProcedure => unreachable!(),
+        }
+    }
+}
+
+/// Stops the evaluation of every node until the reason for unwinding is handled
+enum Unwind<'filepath> {
+    Goto { label: NodeID<BoundNode<'filepath>> },
+    Error(EvalError<'filepath>),
+}
+
+impl<'filepath> From<EvalError<'filepath>> for Unwind<'filepath> {
+    fn from(error: EvalError<'filepath>) -> Self {
+        Unwind::Error(error)
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum EvalError<'filepath> {
+    #[display(fmt = "The result does not fit in the type {typ}")]
+    IntegerOverflow {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        typ: String,
+    },
+    #[display(fmt = "Division by zero")]
+    DivisionByZero {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "The index {index} is out of bounds for a length of {length}")]
+    IndexOutOfBounds {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        index: i128,
+        length: usize,
+    },
+    #[display(fmt = "The start of the slice {low} must not be negative or after its end {high}")]
+    InvalidSliceRange {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        low: i128,
+        high: i128,
+    },
+    #[display(fmt = "Expected the union to hold the type {expected}, but it holds the type {got}")]
+    WrongUnionVariant {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        expected: String,
+        got: String,
+    },
+    #[display(fmt = "Cannot pop from an empty dynamic array")]
+    PopFromEmptyDynamicArray {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Attempted to access memory that has been freed or is out of bounds")]
+    InvalidAddress {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Attempted to dereference a null pointer")]
+    NullDereference {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Attempted to call a null procedure")]
+    NullProcedureCall {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(
+        fmt = "The variable is used before its declaration was run, because a goto jumped over it"
+    )]
+    UninitializedVariable {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+}
+
+impl<'filepath> GetLocation<'filepath> for EvalError<'filepath> {
+    fn get_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            EvalError::IntegerOverflow { location, .. }
+            | EvalError::DivisionByZero { location, .. }
+            | EvalError::IndexOutOfBounds { location, .. }
+            | EvalError::InvalidSliceRange { location, .. }
+            | EvalError::WrongUnionVariant { location, .. }
+            | EvalError::PopFromEmptyDynamicArray { location, .. }
+            | EvalError::InvalidAddress { location, .. }
+            | EvalError::NullDereference { location, .. }
+            | EvalError::NullProcedureCall { location, .. }
+            | EvalError::UninitializedVariable { location, .. } => location,
+        }
+    }
+
+    fn get_end_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            EvalError::IntegerOverflow { end_location, .. }
+            | EvalError::DivisionByZero { end_location, .. }
+            | EvalError::IndexOutOfBounds { end_location, .. }
+            | EvalError::InvalidSliceRange { end_location, .. }
+            | EvalError::WrongUnionVariant { end_location, .. }
+            | EvalError::PopFromEmptyDynamicArray { end_location, .. }
+            | EvalError::InvalidAddress { end_location, .. }
+            | EvalError::NullDereference { end_location, .. }
+            | EvalError::NullProcedureCall { end_location, .. }
+            | EvalError::UninitializedVariable { end_location, .. } => end_location,
         }
-        BoundNode::Procedure { .. } => Value::Procedure { procedure: node },
     }
 }
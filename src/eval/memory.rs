@@ -0,0 +1,66 @@
+use crate::eval::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
+pub struct Address {
+    pub allocation: usize,
+    pub index: usize,
+    /// The indices of the struct members or array elements to go through to get from the value at `index`
+    /// to the addressed value, so that there can be pointers to the members of a variable
+    pub members: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct Memory<'filepath> {
+    /// Freed allocations are set to `None` and never reused, so that dangling addresses can be detected
+    allocations: Vec<Option<Vec<Value<'filepath>>>>,
+}
+
+impl<'filepath> Memory<'filepath> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allocate(&mut self, values: Vec<Value<'filepath>>) -> usize {
+        self.allocations.push(Some(values));
+        self.allocations.len() - 1
+    }
+
+    pub fn free(&mut self, allocation: usize) {
+        self.allocations[allocation] = None;
+    }
+
+    /// Returns `None` if the address is dangling
+    pub fn read(&self, address: &Address) -> Option<&Value<'filepath>> {
+        let mut value = self
+            .allocations
+            .get(address.allocation)?
+            .as_ref()?
+            .get(address.index)?;
+        for &member in &address.members {
+            value = match *value {
+                Value::Struct { ref members } => members.get(member)?,
+                Value::Array { ref elements } => elements.get(member)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    /// Returns `None` if the address is dangling
+    pub fn write(&mut self, address: &Address, value: Value<'filepath>) -> Option<()> {
+        let mut place = self
+            .allocations
+            .get_mut(address.allocation)?
+            .as_mut()?
+            .get_mut(address.index)?;
+        for &member in &address.members {
+            place = match *place {
+                Value::Struct { ref mut members } => members.get_mut(member)?,
+                Value::Array { ref mut elements } => elements.get_mut(member)?,
+                _ => return None,
+            };
+        }
+        *place = value;
+        Some(())
+    }
+}
@@ -1,14 +1,78 @@
 use crate::{
-    binding::{BoundNode, Type},
+    binding::{BoundNode, Builtin, Type},
+    eval::Address,
     nodes::NodeID,
 };
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub enum Value<'filepath> {
+    Void,
     Type {
         typ: NodeID<Type>,
     },
     Procedure {
+        /// `None` is the zero value of a procedure type
+        procedure: Option<NodeID<BoundNode<'filepath>>>,
+    },
+    Builtin {
+        builtin: Builtin,
+    },
+    GenericProcedure {
         procedure: NodeID<BoundNode<'filepath>>,
     },
+    Bool {
+        value: bool,
+    },
+    Integer {
+        value: i128,
+    },
+    Pointer {
+        /// `None` is a null pointer
+        address: Option<Address>,
+    },
+    Slice {
+        data: Option<Address>,
+        length: usize,
+    },
+    DynamicArray {
+        /// Points to an allocation of `capacity` values, the first `length` of which are in the array
+        data: Option<Address>,
+        length: usize,
+        capacity: usize,
+    },
+    Struct {
+        members: Vec<Value<'filepath>>,
+    },
+    Array {
+        elements: Vec<Value<'filepath>>,
+    },
+    Union {
+        /// The variant of the union that is stored
+        typ: NodeID<Type>,
+        value: Box<Value<'filepath>>,
+    },
+}
+
+impl Value<'_> {
+    /// Whether the value has an address in it, which only points to the right memory in the interpreter that made it
+    pub fn has_address(&self) -> bool {
+        match *self {
+            Value::Pointer { ref address } => address.is_some(),
+            Value::Slice { ref data, .. } | Value::DynamicArray { ref data, .. } => data.is_some(),
+            Value::Struct {
+                members: ref values,
+            }
+            | Value::Array {
+                elements: ref values,
+            } => values.iter().any(Value::has_address),
+            Value::Union { ref value, .. } => value.has_address(),
+            Value::Void
+            | Value::Type { .. }
+            | Value::Procedure { .. }
+            | Value::Builtin { .. }
+            | Value::GenericProcedure { .. }
+            | Value::Bool { .. }
+            | Value::Integer { .. } => false,
+        }
+    }
 }
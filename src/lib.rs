@@ -2,6 +2,7 @@
 #![deny(elided_lifetimes_in_paths)]
 
 pub mod binding;
+pub mod diagnostics;
 pub mod eval;
 pub mod nodes;
 pub mod parsing;
@@ -34,7 +34,7 @@ impl<T> Debug for NodeID<T> {
 
 impl<T> Clone for NodeID<T> {
     fn clone(&self) -> Self {
-        Self(self.0, PhantomData)
+        *self
     }
 }
 
@@ -92,6 +92,17 @@ impl<T> Nodes<T> {
     pub fn get_mut(&mut self, id: NodeID<T>) -> Option<&mut T> {
         self.nodes.get_mut(&id)
     }
+
+    /// The nodes in the order that they were inserted
+    pub fn iter(&self) -> impl Iterator<Item = (NodeID<T>, &T)> {
+        let mut nodes = self
+            .nodes
+            .iter()
+            .map(|(&id, node)| (id, node))
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|&(id, _)| id);
+        nodes.into_iter()
+    }
 }
 
 impl<T> Default for Nodes<T> {
@@ -5,39 +5,508 @@ mod ast;
 
 pub use ast::*;
 
+/// Returns every expression in the file along with all the errors found while parsing it,
+/// expressions that could not be parsed are replaced with `Ast::Error`
 pub fn parse_file<'filepath, 'source>(
     filepath: &'filepath str,
     source: &'source str,
-) -> Result<Vec<Ast<'filepath, 'source>>, ParsingError<'filepath, 'source>> {
+) -> (
+    Vec<Ast<'filepath, 'source>>,
+    Vec<ParsingError<'filepath, 'source>>,
+) {
     let mut lexer = Lexer::new(filepath, source);
+    let mut errors = vec![];
+    let expressions = parse_expression_list(&mut lexer, &mut errors, false);
+    (expressions, errors)
+}
+
+/// Parses newline separated expressions until the end of the file, or until the closing brace when `in_block` is true.
+/// When an expression fails to parse, the error is recorded and parsing resumes after the next newline or closing brace
+fn parse_expression_list<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    in_block: bool,
+) -> Vec<Ast<'filepath, 'source>> {
     let mut expressions = vec![];
-    while lexer.peek().is_some() {
-        while let Some(_newline) = match_token(&mut lexer, TokenKind::Newline)? {}
-        if lexer.peek().is_none() {
-            break;
+    loop {
+        match lexer.peek() {
+            None => break,
+            Some(Ok(Token {
+                kind: TokenKind::Newline,
+                ..
+            })) => {
+                lexer.next();
+                continue;
+            }
+            Some(Ok(Token {
+                kind: TokenKind::CloseBrace,
+                ..
+            })) if in_block => break,
+            _ => {}
+        }
+
+        let start = lexer.clone();
+        match parse_expression(lexer, errors) {
+            Ok(expression) => {
+                expressions.push(expression);
+                if let Err(error) = expect_newline(lexer) {
+                    errors.push(error);
+                    synchronize(lexer, in_block);
+                }
+            }
+            Err(error) => {
+                errors.push(error);
+                // the tokens of the failed expression are skipped again from the start,
+                // so that a newline or closing brace consumed while reporting the error is not lost
+                *lexer = start;
+                let (location, end_location) = synchronize(lexer, in_block).expect(
+                    "the failed expression should start with a token that is not a terminator",
+                );
+                expressions.push(Ast::Error {
+                    location,
+                    end_location,
+                });
+            }
         }
-        expressions.push(parse_expression(&mut lexer)?);
-        expect_newline(&mut lexer)?;
     }
-    Ok(expressions)
+    expressions
+}
+
+/// Skips tokens until after the next newline, or until the closing brace of the current block when `in_block` is true.
+/// Newlines and closing braces inside nested blocks do not stop the skipping.
+/// Returns the location of the skipped tokens, if any were skipped
+fn synchronize<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    in_block: bool,
+) -> Option<(SourceLocation<'filepath>, SourceLocation<'filepath>)> {
+    let mut span: Option<(SourceLocation<'filepath>, SourceLocation<'filepath>)> = None;
+    let mut depth = 0usize;
+    while let Some(item) = lexer.peek() {
+        let (location, end_location) = match item {
+            Ok(token) => {
+                match token.kind {
+                    TokenKind::Newline if depth == 0 => {
+                        lexer.next();
+                        break;
+                    }
+                    TokenKind::CloseBrace if depth == 0 && in_block => break,
+                    TokenKind::OpenBrace => depth += 1,
+                    TokenKind::CloseBrace => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                (token.get_location(), token.get_end_location())
+            }
+            Err(error) => (error.get_location(), error.get_end_location()),
+        };
+        lexer.next();
+        span = Some((
+            span.map_or(location, |(start_location, _)| start_location),
+            end_location,
+        ));
+    }
+    span
 }
 
 fn parse_expression<'filepath, 'source>(
     lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
+    parse_conditional_expression(lexer, errors, true)
+}
+
+/// `allow_declarations` is false when a `:` after a name means something else,
+/// like in `name: type : value`, `condition ? then_expr : else_expr` or `operand[low:high]`,
+/// and then assignments are not parsed either, because the `=` in `name: type = value` comes after the type
+fn parse_conditional_expression<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    allow_declarations: bool,
+) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
+    let expression = parse_binary_expression(lexer, errors, 0, allow_declarations)?;
+    if let Some(question_mark_token) = match_token(lexer, TokenKind::QuestionMark)? {
+        let then_expression = parse_conditional_expression(lexer, errors, false)?;
+        let colon_token = match_token(lexer, TokenKind::Colon)?;
+        let else_expression = if colon_token.is_some() {
+            Some(parse_conditional_expression(
+                lexer,
+                errors,
+                allow_declarations,
+            )?)
+        } else {
+            None
+        };
+        Ok(Ast::If {
+            condition: Box::new(expression),
+            question_mark_token,
+            then_expression: Box::new(then_expression),
+            colon_token,
+            else_expression: else_expression.map(Box::new),
+        })
+    } else if let Some(left_right_arrow_token) = match_token(lexer, TokenKind::LeftRightArrow)? {
+        let body = parse_conditional_expression(lexer, errors, allow_declarations)?;
+        Ok(Ast::While {
+            condition: Box::new(expression),
+            left_right_arrow_token,
+            body: Box::new(body),
+        })
+    } else if allow_declarations
+        && matches!(
+            lexer.peek().transpose()?,
+            Some(Token {
+                kind: TokenKind::Equal
+                    | TokenKind::PlusEqual
+                    | TokenKind::MinusEqual
+                    | TokenKind::AsteriskEqual
+                    | TokenKind::SlashEqual,
+                ..
+            })
+        )
+    {
+        let operator_token = next_token(lexer)?;
+        let value = parse_conditional_expression(lexer, errors, allow_declarations)?;
+        Ok(Ast::Assignment {
+            place: Box::new(expression),
+            operator_token,
+            value: Box::new(value),
+        })
+    } else {
+        Ok(expression)
+    }
+}
+
+fn parse_binary_expression<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    parent_precedence: usize,
+    allow_declarations: bool,
 ) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
-    let mut expression = match next_token(lexer)? {
+    let mut left = parse_unary_expression(lexer, errors, allow_declarations)?;
+    loop {
+        let precedence = match lexer.peek().transpose()? {
+            Some(ref token) => binary_precedence(&token.kind),
+            None => 0,
+        };
+        if precedence <= parent_precedence {
+            break;
+        }
+
+        let operator_token = next_token(lexer)?;
+        let right = parse_binary_expression(lexer, errors, precedence, allow_declarations)?;
+        left = Ast::Binary {
+            left: Box::new(left),
+            operator_token,
+            right: Box::new(right),
+        };
+    }
+    Ok(left)
+}
+
+fn parse_unary_expression<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    allow_declarations: bool,
+) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
+    if let Some(Token {
+        kind: TokenKind::Plus | TokenKind::Minus | TokenKind::ExclamationMark,
+        ..
+    }) = lexer.peek().transpose()?
+    {
+        let operator_token = next_token(lexer)?;
+        let operand = parse_unary_expression(lexer, errors, allow_declarations)?;
+        Ok(Ast::Unary {
+            operator_token,
+            operand: Box::new(operand),
+        })
+    } else if let Some(ampersand_token) = match_token(lexer, TokenKind::Ampersand)? {
+        let operand = parse_unary_expression(lexer, errors, false)?;
+        Ok(Ast::AddressOf {
+            ampersand_token,
+            operand: Box::new(operand),
+        })
+    } else {
+        parse_primary_expression(lexer, errors, allow_declarations)
+    }
+}
+
+fn binary_precedence(kind: &TokenKind<'_>) -> usize {
+    match *kind {
+        // union types bind the tightest so that `T == int | bool` compares against the union
+        TokenKind::Pipe => 7,
+        TokenKind::Asterisk | TokenKind::Slash | TokenKind::Percent => 6,
+        TokenKind::Plus | TokenKind::Minus => 5,
+        TokenKind::LessThan
+        | TokenKind::LessThanEqual
+        | TokenKind::GreaterThan
+        | TokenKind::GreaterThanEqual => 4,
+        TokenKind::EqualEqual | TokenKind::ExclamationMarkEqual => 3,
+        TokenKind::AmpersandAmpersand => 2,
+        TokenKind::PipePipe => 1,
+        _ => 0,
+    }
+}
+
+fn parse_primary_expression<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    allow_declarations: bool,
+) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
+    let mut expression = parse_operand(lexer, errors, allow_declarations)?;
+
+    loop {
+        expression = if let Some(open_parenthesis_token) =
+            match_token(lexer, TokenKind::OpenParenthesis)?
+        {
+            let mut arguments = vec![];
+            while !matches!(
+                lexer.peek().transpose()?,
+                Some(Token {
+                    kind: TokenKind::CloseParenthesis,
+                    ..
+                })
+            ) {
+                // `name = value` is parsed as an assignment, but in the arguments it is a named argument
+                arguments.push(match parse_expression(lexer, errors)? {
+                    Ast::Assignment {
+                        place,
+                        operator_token:
+                            equal_token @ Token {
+                                kind: TokenKind::Equal,
+                                ..
+                            },
+                        value,
+                    } if matches!(*place, Ast::Name { .. }) => {
+                        let Ast::Name { name_token } = *place else {
+                            unreachable!()
+                        };
+                        Ast::NamedArgument {
+                            name_token,
+                            equal_token,
+                            value,
+                        }
+                    }
+                    argument => argument,
+                });
+                expect_comma(lexer)?;
+            }
+            let close_parenthesis_token = expect_token(lexer, TokenKind::CloseParenthesis)?;
+            Ast::Call {
+                operand: Box::new(expression),
+                open_parenthesis_token,
+                arguments,
+                close_parenthesis_token,
+            }
+        } else if let Some(open_square_bracket_token) =
+            match_token(lexer, TokenKind::OpenSquareBracket)?
+        {
+            let low = if let Some(Token {
+                kind: TokenKind::Colon,
+                ..
+            }) = lexer.peek().transpose()?
+            {
+                None
+            } else {
+                Some(parse_conditional_expression(lexer, errors, false)?)
+            };
+            if let Some(colon_token) = match_token(lexer, TokenKind::Colon)? {
+                let high = if let Some(Token {
+                    kind: TokenKind::CloseSquareBracket,
+                    ..
+                }) = lexer.peek().transpose()?
+                {
+                    None
+                } else {
+                    Some(parse_conditional_expression(lexer, errors, false)?)
+                };
+                let close_square_bracket_token =
+                    expect_token(lexer, TokenKind::CloseSquareBracket)?;
+                Ast::Slice {
+                    operand: Box::new(expression),
+                    open_square_bracket_token,
+                    low: low.map(Box::new),
+                    colon_token,
+                    high: high.map(Box::new),
+                    close_square_bracket_token,
+                }
+            } else {
+                let close_square_bracket_token =
+                    expect_token(lexer, TokenKind::CloseSquareBracket)?;
+                Ast::Index {
+                    operand: Box::new(expression),
+                    open_square_bracket_token,
+                    // there is always an index when there is no colon
+                    index: Box::new(low.unwrap()),
+                    close_square_bracket_token,
+                }
+            }
+        } else if let Some(caret_token) = match_token(lexer, TokenKind::Caret)? {
+            Ast::Dereference {
+                operand: Box::new(expression),
+                caret_token,
+            }
+        } else if let Some(period_token) = match_token(lexer, TokenKind::Period)? {
+            if let Some(open_parenthesis_token) = match_token(lexer, TokenKind::OpenParenthesis)? {
+                let index = parse_expression(lexer, errors)?;
+                let close_parenthesis_token = expect_token(lexer, TokenKind::CloseParenthesis)?;
+                expression = Ast::MemberIndex {
+                    operand: Box::new(expression),
+                    period_token,
+                    open_parenthesis_token,
+                    index: Box::new(index),
+                    close_parenthesis_token,
+                };
+                continue;
+            }
+
+            let member_name_token = next_token(lexer)?;
+            if !matches!(
+                member_name_token.kind,
+                TokenKind::Name(_) | TokenKind::Integer(_)
+            ) {
+                return Err(ParsingError::ExpectedNameToken {
+                    got: member_name_token,
+                });
+            }
+            Ast::MemberAccess {
+                operand: Box::new(expression),
+                period_token,
+                member_name_token,
+            }
+        } else {
+            break;
+        };
+    }
+
+    Ok(expression)
+}
+
+/// Whether the parameters of a procedure are followed by its `->` or the `{` of its body, the `{` only counts when
+/// `allow_declarations` is true so that in `(a: T) -> (b: T) { ... }` the return type is not a procedure
+fn is_procedure_next<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    allow_declarations: bool,
+) -> Result<bool, ParsingError<'filepath, 'source>> {
+    Ok(match lexer.peek().transpose()? {
+        Some(Token {
+            kind: TokenKind::RightArrow,
+            ..
+        }) => true,
+        Some(Token {
+            kind: TokenKind::OpenBrace,
+            ..
+        }) => allow_declarations,
+        _ => false,
+    })
+}
+
+/// A declaration, or a name for a parameter that gets its type from where the procedure is used
+fn is_parameter(expression: &Ast<'_, '_>) -> bool {
+    matches!(expression, Ast::Declaration { .. } | Ast::Name { .. })
+}
+
+/// Parses the rest of a procedure or a procedure type after its parameters, which has to start with `->` or `{`
+fn parse_procedure<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    open_parenthesis_token: Token<'filepath, 'source>,
+    parameters: Vec<Ast<'filepath, 'source>>,
+    close_parenthesis_token: Token<'filepath, 'source>,
+) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
+    let right_arrow_token = match_token(lexer, TokenKind::RightArrow)?;
+    let return_type = if right_arrow_token.is_some() {
+        // `{` cannot start a procedure here, because it is the body of this one
+        Some(parse_conditional_expression(lexer, errors, false)?)
+    } else {
+        None
+    };
+
+    if let Some(open_brace_token) = match_token(lexer, TokenKind::OpenBrace)? {
+        let body = parse_block(lexer, errors, open_brace_token)?;
+        Ok(Ast::Procedure {
+            open_parenthesis_token,
+            parameters,
+            close_parenthesis_token,
+            right_arrow_token,
+            return_type: return_type.map(Box::new),
+            body: Box::new(body),
+        })
+    } else {
+        // without a `->` there would have been a `{`
+        Ok(Ast::ProcedureType {
+            open_parenthesis_token,
+            parameters,
+            close_parenthesis_token,
+            right_arrow_token: right_arrow_token.unwrap(),
+            return_type: Box::new(return_type.unwrap()),
+        })
+    }
+}
+
+/// An expression without any postfix operators, so that in `[]int(value)`
+/// the type operator applies to `int` and the whole type is called
+fn parse_operand<'filepath, 'source>(
+    lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
+    allow_declarations: bool,
+) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
+    Ok(match next_token(lexer)? {
         open_parenthesis_token @ Token {
             kind: TokenKind::OpenParenthesis,
             ..
         } => {
-            let expression = parse_expression(lexer)?;
-            if match_token(lexer, TokenKind::Comma)?.is_some() {
-                if !matches!(expression, Ast::Declaration { .. }) {
-                    return Err(ParsingError::ExpectedDeclarationForProcedureOrStruct {
-                        location: expression.get_location(),
-                    });
-                }
+            // `()` is an empty parameter or member list
+            if let Some(close_parenthesis_token) = match_token(lexer, TokenKind::CloseParenthesis)?
+            {
+                return Ok(if is_procedure_next(lexer, allow_declarations)? {
+                    parse_procedure(
+                        lexer,
+                        errors,
+                        open_parenthesis_token,
+                        vec![],
+                        close_parenthesis_token,
+                    )?
+                } else {
+                    Ast::StructType {
+                        open_parenthesis_token,
+                        members: vec![],
+                        close_parenthesis_token,
+                    }
+                });
+            }
 
+            let expression = parse_expression(lexer, errors)?;
+            if !matches!(expression, Ast::Declaration { .. })
+                && match_token(lexer, TokenKind::Comma)?.is_some()
+            {
+                match_token(lexer, TokenKind::Newline)?;
+                let mut values = vec![expression];
+                while !matches!(
+                    lexer.peek().transpose()?,
+                    Some(Token {
+                        kind: TokenKind::CloseParenthesis,
+                        ..
+                    })
+                ) {
+                    values.push(parse_expression(lexer, errors)?);
+                    expect_comma(lexer)?;
+                }
+                let close_parenthesis_token = expect_token(lexer, TokenKind::CloseParenthesis)?;
+                if values.iter().all(is_parameter) && is_procedure_next(lexer, allow_declarations)?
+                {
+                    parse_procedure(
+                        lexer,
+                        errors,
+                        open_parenthesis_token,
+                        values,
+                        close_parenthesis_token,
+                    )?
+                } else {
+                    Ast::StructValue {
+                        open_parenthesis_token,
+                        values,
+                        close_parenthesis_token,
+                    }
+                }
+            } else if match_token(lexer, TokenKind::Comma)?.is_some() {
                 let mut declarations = vec![expression];
 
                 while !matches!(
@@ -47,38 +516,54 @@ fn parse_expression<'filepath, 'source>(
                         ..
                     })
                 ) {
+                    let dollar_token = match_token(lexer, TokenKind::Dollar)?;
                     let name_token = next_token(lexer)?;
                     if !matches!(name_token.kind, TokenKind::Name(_)) {
                         return Err(ParsingError::ExpectedNameToken { got: name_token });
                     }
-                    let colon_token = expect_token(lexer, TokenKind::Colon)?;
 
-                    let typ = if !matches!(
-                        lexer.peek().transpose()?,
-                        Some(Token {
-                            kind: TokenKind::Equal,
-                            ..
-                        })
-                    ) {
-                        Some(parse_expression(lexer)?)
+                    // a procedure parameter without a type gets it from where the procedure is used
+                    if dollar_token.is_none()
+                        && !matches!(
+                            lexer.peek().transpose()?,
+                            Some(Token {
+                                kind: TokenKind::Colon,
+                                ..
+                            })
+                        )
+                    {
+                        declarations.push(Ast::Name { name_token });
                     } else {
-                        None
-                    };
+                        let colon_token = expect_token(lexer, TokenKind::Colon)?;
 
-                    let equals_token = match_token(lexer, TokenKind::Equal)?;
-                    let value = if equals_token.is_some() {
-                        Some(parse_expression(lexer)?)
-                    } else {
-                        None
-                    };
+                        let typ = if !matches!(
+                            lexer.peek().transpose()?,
+                            Some(Token {
+                                kind: TokenKind::Equal,
+                                ..
+                            })
+                        ) {
+                            Some(parse_conditional_expression(lexer, errors, false)?)
+                        } else {
+                            None
+                        };
 
-                    declarations.push(Ast::Declaration {
-                        name_token,
-                        colon_token,
-                        typ: typ.map(Box::new),
-                        equals_token,
-                        value: value.map(Box::new),
-                    });
+                        let equals_token = match_token(lexer, TokenKind::Equal)?;
+                        let value = if equals_token.is_some() {
+                            Some(parse_expression(lexer, errors)?)
+                        } else {
+                            None
+                        };
+
+                        declarations.push(Ast::Declaration {
+                            dollar_token,
+                            name_token,
+                            colon_token,
+                            typ: typ.map(Box::new),
+                            equals_token,
+                            value: value.map(Box::new),
+                        });
+                    }
 
                     if matches!(
                         lexer.peek().transpose()?,
@@ -95,28 +580,24 @@ fn parse_expression<'filepath, 'source>(
 
                 let close_parenthesis_token = expect_token(lexer, TokenKind::CloseParenthesis)?;
 
-                if let Some(right_arrow_token) = match_token(lexer, TokenKind::RightArrow)? {
-                    let return_type = parse_expression(lexer)?;
-                    if let Some(open_brace_token) = match_token(lexer, TokenKind::OpenBrace)? {
-                        let body = parse_block(lexer, open_brace_token)?;
-                        Ast::Procedure {
-                            open_parenthesis_token,
-                            parameters: declarations,
-                            close_parenthesis_token,
-                            right_arrow_token,
-                            return_type: Box::new(return_type),
-                            body: Box::new(body),
-                        }
-                    } else {
-                        Ast::ProcedureType {
-                            open_parenthesis_token,
-                            parameters: declarations,
-                            close_parenthesis_token,
-                            right_arrow_token,
-                            return_type: Box::new(return_type),
-                        }
-                    }
+                if is_procedure_next(lexer, allow_declarations)? {
+                    parse_procedure(
+                        lexer,
+                        errors,
+                        open_parenthesis_token,
+                        declarations,
+                        close_parenthesis_token,
+                    )?
                 } else {
+                    if let Some(member) = declarations
+                        .iter()
+                        .find(|member| !matches!(member, Ast::Declaration { .. }))
+                    {
+                        return Err(ParsingError::ExpectedStructMemberType {
+                            location: member.get_location(),
+                            end_location: member.get_end_location(),
+                        });
+                    }
                     Ast::StructType {
                         open_parenthesis_token,
                         members: declarations,
@@ -125,34 +606,31 @@ fn parse_expression<'filepath, 'source>(
                 }
             } else {
                 let close_parenthesis_token = expect_token(lexer, TokenKind::CloseParenthesis)?;
-                if let Some(right_arrow_token) = match_token(lexer, TokenKind::RightArrow)? {
-                    if !matches!(expression, Ast::Declaration { .. }) {
-                        return Err(ParsingError::ExpectedDeclarationForProcedure {
-                            location: expression.get_location(),
-                        });
-                    }
-
-                    let parameters = vec![expression];
-
-                    let return_type = parse_expression(lexer)?;
-                    if let Some(open_brace_token) = match_token(lexer, TokenKind::OpenBrace)? {
-                        let body = parse_block(lexer, open_brace_token)?;
-                        Ast::Procedure {
-                            open_parenthesis_token,
-                            parameters,
-                            close_parenthesis_token,
-                            right_arrow_token,
-                            return_type: Box::new(return_type),
-                            body: Box::new(body),
-                        }
-                    } else {
-                        Ast::ProcedureType {
-                            open_parenthesis_token,
-                            parameters,
-                            close_parenthesis_token,
-                            right_arrow_token,
-                            return_type: Box::new(return_type),
-                        }
+                if is_parameter(&expression) && is_procedure_next(lexer, allow_declarations)? {
+                    parse_procedure(
+                        lexer,
+                        errors,
+                        open_parenthesis_token,
+                        vec![expression],
+                        close_parenthesis_token,
+                    )?
+                } else if matches!(
+                    lexer.peek().transpose()?,
+                    Some(Token {
+                        kind: TokenKind::RightArrow,
+                        ..
+                    })
+                ) {
+                    return Err(ParsingError::ExpectedDeclarationForProcedure {
+                        location: expression.get_location(),
+                        end_location: expression.get_end_location(),
+                    });
+                } else if matches!(expression, Ast::Declaration { .. }) {
+                    // `(a: int)` is a struct with one member, like `(a: int,)`
+                    Ast::StructType {
+                        open_parenthesis_token,
+                        members: vec![expression],
+                        close_parenthesis_token,
                     }
                 } else {
                     Ast::ParenthesisedExpression {
@@ -163,12 +641,16 @@ fn parse_expression<'filepath, 'source>(
                 }
             }
         }
-
         name_token @ Token {
             kind: TokenKind::Name(_),
             ..
         } => {
-            if let Some(colon_token) = match_token(lexer, TokenKind::Colon)? {
+            let colon_token = if allow_declarations {
+                match_token(lexer, TokenKind::Colon)?
+            } else {
+                None
+            };
+            if let Some(colon_token) = colon_token {
                 let typ = if !matches!(
                     lexer.peek().transpose()?,
                     Some(Token {
@@ -176,13 +658,13 @@ fn parse_expression<'filepath, 'source>(
                         ..
                     })
                 ) {
-                    Some(parse_expression(lexer)?)
+                    Some(parse_conditional_expression(lexer, errors, false)?)
                 } else {
                     None
                 };
 
                 if let Some(colon_equals_token) = match_token(lexer, TokenKind::Colon)? {
-                    let value = parse_expression(lexer)?;
+                    let value = parse_expression(lexer, errors)?;
                     Ast::Constant {
                         name_token,
                         colon_token,
@@ -193,11 +675,12 @@ fn parse_expression<'filepath, 'source>(
                 } else {
                     let equals_token = match_token(lexer, TokenKind::Equal)?;
                     let value = if equals_token.is_some() {
-                        Some(parse_expression(lexer)?)
+                        Some(parse_expression(lexer, errors)?)
                     } else {
                         None
                     };
                     Ast::Declaration {
+                        dollar_token: None,
                         name_token,
                         colon_token,
                         typ: typ.map(Box::new),
@@ -210,6 +693,80 @@ fn parse_expression<'filepath, 'source>(
             }
         }
 
+        dollar_token @ Token {
+            kind: TokenKind::Dollar,
+            ..
+        } if allow_declarations => {
+            let name_token = next_token(lexer)?;
+            if !matches!(name_token.kind, TokenKind::Name(_)) {
+                return Err(ParsingError::ExpectedNameToken { got: name_token });
+            }
+            let colon_token = expect_token(lexer, TokenKind::Colon)?;
+
+            let typ = if !matches!(
+                lexer.peek().transpose()?,
+                Some(Token {
+                    kind: TokenKind::Equal,
+                    ..
+                })
+            ) {
+                Some(parse_conditional_expression(lexer, errors, false)?)
+            } else {
+                None
+            };
+
+            let equals_token = match_token(lexer, TokenKind::Equal)?;
+            let value = if equals_token.is_some() {
+                Some(parse_expression(lexer, errors)?)
+            } else {
+                None
+            };
+
+            Ast::Declaration {
+                dollar_token: Some(dollar_token),
+                name_token,
+                colon_token,
+                typ: typ.map(Box::new),
+                equals_token,
+                value: value.map(Box::new),
+            }
+        }
+
+        integer_token @ Token {
+            kind: TokenKind::Integer(_),
+            ..
+        } => Ast::Integer { integer_token },
+
+        open_brace_token @ Token {
+            kind: TokenKind::OpenBrace,
+            ..
+        } => parse_block(lexer, errors, open_brace_token)?,
+
+        right_arrow_token @ Token {
+            kind: TokenKind::RightArrow,
+            ..
+        } => {
+            let name_token = next_token(lexer)?;
+            if !matches!(name_token.kind, TokenKind::Name(_)) {
+                return Err(ParsingError::ExpectedNameToken { got: name_token });
+            }
+            Ast::Goto {
+                right_arrow_token,
+                name_token,
+            }
+        }
+
+        caret_token @ Token {
+            kind: TokenKind::Caret,
+            ..
+        } => {
+            let operand = parse_operand(lexer, errors, false)?;
+            Ast::PointerType {
+                caret_token,
+                operand: Box::new(operand),
+            }
+        }
+
         open_square_bracket_token @ Token {
             kind: TokenKind::OpenSquareBracket,
             ..
@@ -217,16 +774,26 @@ fn parse_expression<'filepath, 'source>(
             if let Some(close_square_bracket_token) =
                 match_token(lexer, TokenKind::CloseSquareBracket)?
             {
-                let operand = parse_expression(lexer)?;
+                let operand = parse_operand(lexer, errors, false)?;
                 Ast::SliceType {
                     open_square_bracket_token,
                     close_square_bracket_token,
                     operand: Box::new(operand),
                 }
+            } else if let Some(period_period_token) = match_token(lexer, TokenKind::PeriodPeriod)? {
+                let close_square_bracket_token =
+                    expect_token(lexer, TokenKind::CloseSquareBracket)?;
+                let operand = parse_operand(lexer, errors, false)?;
+                Ast::DynamicArrayType {
+                    open_square_bracket_token,
+                    period_period_token,
+                    close_square_bracket_token,
+                    operand: Box::new(operand),
+                }
             } else if let Some(caret_token) = match_token(lexer, TokenKind::Caret)? {
                 let close_square_bracket_token =
                     expect_token(lexer, TokenKind::CloseSquareBracket)?;
-                let operand = parse_expression(lexer)?;
+                let operand = parse_operand(lexer, errors, false)?;
                 Ast::MultipointerType {
                     open_square_bracket_token,
                     caret_token,
@@ -234,10 +801,10 @@ fn parse_expression<'filepath, 'source>(
                     operand: Box::new(operand),
                 }
             } else {
-                let length = parse_expression(lexer)?;
+                let length = parse_expression(lexer, errors)?;
                 let close_square_bracket_token =
                     expect_token(lexer, TokenKind::CloseSquareBracket)?;
-                let operand = parse_expression(lexer)?;
+                let operand = parse_operand(lexer, errors, false)?;
                 Ast::ArrayType {
                     open_square_bracket_token,
                     length: Box::new(length),
@@ -248,74 +815,15 @@ fn parse_expression<'filepath, 'source>(
         }
 
         token => return Err(ParsingError::UnexpectedToken(token)),
-    };
-
-    loop {
-        expression =
-            if let Some(open_parenthesis_token) = match_token(lexer, TokenKind::OpenParenthesis)? {
-                let mut arguments = vec![];
-                while !matches!(
-                    lexer.peek().transpose()?,
-                    Some(Token {
-                        kind: TokenKind::CloseParenthesis,
-                        ..
-                    })
-                ) {
-                    arguments.push(parse_expression(lexer)?);
-                    expect_comma(lexer)?;
-                }
-                let close_parenthesis_token = expect_token(lexer, TokenKind::CloseParenthesis)?;
-                Ast::Call {
-                    operand: Box::new(expression),
-                    open_parenthesis_token,
-                    arguments,
-                    close_parenthesis_token,
-                }
-            } else if let Some(period_token) = match_token(lexer, TokenKind::Period)? {
-                let member_name_token = next_token(lexer)?;
-                if !matches!(member_name_token.kind, TokenKind::Name(_)) {
-                    return Err(ParsingError::ExpectedNameToken {
-                        got: member_name_token,
-                    });
-                }
-                Ast::MemberAccess {
-                    operand: Box::new(expression),
-                    period_token,
-                    member_name_token,
-                }
-            } else {
-                break;
-            };
-    }
-
-    Ok(expression)
+    })
 }
 
 fn parse_block<'filepath, 'source>(
     lexer: &mut Lexer<'filepath, 'source>,
+    errors: &mut Vec<ParsingError<'filepath, 'source>>,
     open_brace_token: Token<'filepath, 'source>,
 ) -> Result<Ast<'filepath, 'source>, ParsingError<'filepath, 'source>> {
-    let mut expressions = vec![];
-    while !matches!(
-        lexer.peek().transpose()?,
-        Some(Token {
-            kind: TokenKind::CloseBrace,
-            ..
-        })
-    ) {
-        while let Some(_newline) = match_token(lexer, TokenKind::Newline)? {}
-        if matches!(
-            lexer.peek().transpose()?,
-            Some(Token {
-                kind: TokenKind::CloseBrace,
-                ..
-            })
-        ) {
-            break;
-        }
-        expressions.push(parse_expression(lexer)?);
-        expect_newline(lexer)?;
-    }
+    let expressions = parse_expression_list(lexer, errors, true);
     let close_brace_token = expect_token(lexer, TokenKind::CloseBrace)?;
     Ok(Ast::Block {
         open_brace_token,
@@ -330,7 +838,7 @@ fn next_token<'filepath, 'source>(
     lexer
         .next()
         .transpose()?
-        .ok_or_else(|| ParsingError::UnexpectedEOF(lexer.get_filepath()))
+        .ok_or_else(|| ParsingError::UnexpectedEOF(lexer.get_location()))
 }
 
 fn match_token<'filepath, 'source>(
@@ -422,24 +930,53 @@ fn expect_comma<'filepath, 'source>(
 pub enum ParsingError<'filepath, 'source> {
     #[display(fmt = "{_0}")]
     LexerError(LexerError<'filepath>),
-    #[display(fmt = "Unexpected EOF in {_0}")]
-    UnexpectedEOF(&'filepath str),
-    #[display(fmt = "{}: Unexpected token {_0}", "_0.get_location()")]
+    #[display(fmt = "Unexpected end of file")]
+    UnexpectedEOF(SourceLocation<'filepath>),
+    #[display(fmt = "Unexpected token {_0}")]
     UnexpectedToken(Token<'filepath, 'source>),
-    #[display(
-        fmt = "{}: Expected token {expected}, but got {got}",
-        "got.get_location()"
-    )]
+    #[display(fmt = "Expected token {expected}, but got {got}")]
     ExpectedToken {
         expected: TokenKind<'source>,
         got: Token<'filepath, 'source>,
     },
-    #[display(fmt = "{}: Expected name token, but got {got}", "got.get_location()")]
+    #[display(fmt = "Expected name token, but got {got}")]
     ExpectedNameToken { got: Token<'filepath, 'source> },
-    #[display(fmt = "{location}: Expected a declaration for procedure parameter")]
-    ExpectedDeclarationForProcedure { location: SourceLocation<'filepath> },
-    #[display(fmt = "{location}: Expected a declaration for procedure parameter or struct field")]
-    ExpectedDeclarationForProcedureOrStruct { location: SourceLocation<'filepath> },
+    #[display(fmt = "Expected a declaration for procedure parameter")]
+    ExpectedDeclarationForProcedure {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+    #[display(fmt = "Expected a type for the struct member")]
+    ExpectedStructMemberType {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+}
+
+impl<'filepath, 'source> GetLocation<'filepath> for ParsingError<'filepath, 'source> {
+    fn get_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            ParsingError::LexerError(ref error) => error.get_location(),
+            ParsingError::UnexpectedEOF(location) => location,
+            ParsingError::UnexpectedToken(ref token) => token.get_location(),
+            ParsingError::ExpectedToken { ref got, .. } => got.get_location(),
+            ParsingError::ExpectedNameToken { ref got } => got.get_location(),
+            ParsingError::ExpectedDeclarationForProcedure { location, .. } => location,
+            ParsingError::ExpectedStructMemberType { location, .. } => location,
+        }
+    }
+
+    fn get_end_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            ParsingError::LexerError(ref error) => error.get_end_location(),
+            ParsingError::UnexpectedEOF(location) => location,
+            ParsingError::UnexpectedToken(ref token) => token.get_end_location(),
+            ParsingError::ExpectedToken { ref got, .. } => got.get_end_location(),
+            ParsingError::ExpectedNameToken { ref got } => got.get_end_location(),
+            ParsingError::ExpectedDeclarationForProcedure { end_location, .. } => end_location,
+            ParsingError::ExpectedStructMemberType { end_location, .. } => end_location,
+        }
+    }
 }
 
 impl<'filepath, 'source> From<LexerError<'filepath>> for ParsingError<'filepath, 'source> {
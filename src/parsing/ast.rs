@@ -15,6 +15,8 @@ pub enum Ast<'filepath, 'source> {
         value: Box<Ast<'filepath, 'source>>,
     },
     Declaration {
+        /// The `$` of a compile time parameter like `$T: type`
+        dollar_token: Option<Token<'filepath, 'source>>,
         name_token: Token<'filepath, 'source>,
         colon_token: Token<'filepath, 'source>,
         typ: Option<Box<Ast<'filepath, 'source>>>,
@@ -24,22 +26,93 @@ pub enum Ast<'filepath, 'source> {
     Name {
         name_token: Token<'filepath, 'source>,
     },
+    Integer {
+        integer_token: Token<'filepath, 'source>,
+    },
     ParenthesisedExpression {
         open_parenthesis_token: Token<'filepath, 'source>,
         expression: Box<Ast<'filepath, 'source>>,
         close_parenthesis_token: Token<'filepath, 'source>,
     },
+    Unary {
+        operator_token: Token<'filepath, 'source>,
+        operand: Box<Ast<'filepath, 'source>>,
+    },
+    /// `&operand`
+    AddressOf {
+        ampersand_token: Token<'filepath, 'source>,
+        operand: Box<Ast<'filepath, 'source>>,
+    },
+    /// `operand^`
+    Dereference {
+        operand: Box<Ast<'filepath, 'source>>,
+        caret_token: Token<'filepath, 'source>,
+    },
+    Binary {
+        left: Box<Ast<'filepath, 'source>>,
+        operator_token: Token<'filepath, 'source>,
+        right: Box<Ast<'filepath, 'source>>,
+    },
+    /// `place = value`, or a compound assignment like `place += value`
+    Assignment {
+        place: Box<Ast<'filepath, 'source>>,
+        operator_token: Token<'filepath, 'source>,
+        value: Box<Ast<'filepath, 'source>>,
+    },
+    If {
+        condition: Box<Ast<'filepath, 'source>>,
+        question_mark_token: Token<'filepath, 'source>,
+        then_expression: Box<Ast<'filepath, 'source>>,
+        colon_token: Option<Token<'filepath, 'source>>,
+        else_expression: Option<Box<Ast<'filepath, 'source>>>,
+    },
+    While {
+        condition: Box<Ast<'filepath, 'source>>,
+        left_right_arrow_token: Token<'filepath, 'source>,
+        body: Box<Ast<'filepath, 'source>>,
+    },
+    Goto {
+        right_arrow_token: Token<'filepath, 'source>,
+        name_token: Token<'filepath, 'source>,
+    },
     MemberAccess {
         operand: Box<Ast<'filepath, 'source>>,
         period_token: Token<'filepath, 'source>,
+        /// Either a name, or an integer for getting a struct member by its index
         member_name_token: Token<'filepath, 'source>,
     },
+    /// `operand.(index)`, which indexes a struct with a value that can be known only at runtime,
+    /// or extracts a variant from a union when the index is a type
+    MemberIndex {
+        operand: Box<Ast<'filepath, 'source>>,
+        period_token: Token<'filepath, 'source>,
+        open_parenthesis_token: Token<'filepath, 'source>,
+        index: Box<Ast<'filepath, 'source>>,
+        close_parenthesis_token: Token<'filepath, 'source>,
+    },
+    /// `operand[index]`
+    Index {
+        operand: Box<Ast<'filepath, 'source>>,
+        open_square_bracket_token: Token<'filepath, 'source>,
+        index: Box<Ast<'filepath, 'source>>,
+        close_square_bracket_token: Token<'filepath, 'source>,
+    },
+    /// `operand[low:high]`, where both bounds can be left out
+    Slice {
+        operand: Box<Ast<'filepath, 'source>>,
+        open_square_bracket_token: Token<'filepath, 'source>,
+        low: Option<Box<Ast<'filepath, 'source>>>,
+        colon_token: Token<'filepath, 'source>,
+        high: Option<Box<Ast<'filepath, 'source>>>,
+        close_square_bracket_token: Token<'filepath, 'source>,
+    },
     Procedure {
         open_parenthesis_token: Token<'filepath, 'source>,
         parameters: Vec<Ast<'filepath, 'source>>,
         close_parenthesis_token: Token<'filepath, 'source>,
-        right_arrow_token: Token<'filepath, 'source>,
-        return_type: Box<Ast<'filepath, 'source>>,
+        /// The return type is inferred from the body if there is no `->`
+        right_arrow_token: Option<Token<'filepath, 'source>>,
+        return_type: Option<Box<Ast<'filepath, 'source>>>,
         body: Box<Ast<'filepath, 'source>>,
     },
     ProcedureType {
@@ -55,6 +128,17 @@ pub enum Ast<'filepath, 'source> {
         arguments: Vec<Ast<'filepath, 'source>>,
         close_parenthesis_token: Token<'filepath, 'source>,
     },
+    /// `name = value` in the arguments of a call, which gives the value to the parameter or member with that name
+    NamedArgument {
+        name_token: Token<'filepath, 'source>,
+        equal_token: Token<'filepath, 'source>,
+        value: Box<Ast<'filepath, 'source>>,
+    },
+    StructValue {
+        open_parenthesis_token: Token<'filepath, 'source>,
+        values: Vec<Ast<'filepath, 'source>>,
+        close_parenthesis_token: Token<'filepath, 'source>,
+    },
     StructType {
         open_parenthesis_token: Token<'filepath, 'source>,
         members: Vec<Ast<'filepath, 'source>>,
@@ -71,12 +155,27 @@ pub enum Ast<'filepath, 'source> {
         close_square_bracket_token: Token<'filepath, 'source>,
         operand: Box<Ast<'filepath, 'source>>,
     },
+    DynamicArrayType {
+        open_square_bracket_token: Token<'filepath, 'source>,
+        period_period_token: Token<'filepath, 'source>,
+        close_square_bracket_token: Token<'filepath, 'source>,
+        operand: Box<Ast<'filepath, 'source>>,
+    },
+    PointerType {
+        caret_token: Token<'filepath, 'source>,
+        operand: Box<Ast<'filepath, 'source>>,
+    },
     MultipointerType {
         open_square_bracket_token: Token<'filepath, 'source>,
         caret_token: Token<'filepath, 'source>,
         close_square_bracket_token: Token<'filepath, 'source>,
         operand: Box<Ast<'filepath, 'source>>,
     },
+    /// The tokens of an expression that failed to parse
+    Error {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
 }
 
 impl<'filepath, 'source> GetLocation<'filepath> for Ast<'filepath, 'source> {
@@ -87,13 +186,37 @@ impl<'filepath, 'source> GetLocation<'filepath> for Ast<'filepath, 'source> {
                 ..
             } => open_brace_token.get_location(),
             Ast::Constant { ref name_token, .. } => name_token.get_location(),
-            Ast::Declaration { ref name_token, .. } => name_token.get_location(),
+            Ast::Declaration {
+                ref dollar_token,
+                ref name_token,
+                ..
+            } => dollar_token.as_ref().unwrap_or(name_token).get_location(),
             Ast::Name { ref name_token } => name_token.get_location(),
+            Ast::Integer { ref integer_token } => integer_token.get_location(),
             Self::ParenthesisedExpression {
                 ref open_parenthesis_token,
                 ..
             } => open_parenthesis_token.get_location(),
+            Ast::Unary {
+                ref operator_token, ..
+            } => operator_token.get_location(),
+            Ast::AddressOf {
+                ref ampersand_token,
+                ..
+            } => ampersand_token.get_location(),
+            Ast::Dereference { ref operand, .. } => operand.get_location(),
+            Ast::Binary { ref left, .. } => left.get_location(),
+            Ast::Assignment { ref place, .. } => place.get_location(),
+            Ast::If { ref condition, .. } => condition.get_location(),
+            Ast::While { ref condition, .. } => condition.get_location(),
+            Ast::Goto {
+                ref right_arrow_token,
+                ..
+            } => right_arrow_token.get_location(),
             Ast::MemberAccess { ref operand, .. } => operand.get_location(),
+            Ast::MemberIndex { ref operand, .. } => operand.get_location(),
+            Ast::Index { ref operand, .. } => operand.get_location(),
+            Ast::Slice { ref operand, .. } => operand.get_location(),
             Ast::Procedure {
                 ref open_parenthesis_token,
                 ..
@@ -103,6 +226,11 @@ impl<'filepath, 'source> GetLocation<'filepath> for Ast<'filepath, 'source> {
                 ..
             } => open_parenthesis_token.get_location(),
             Ast::Call { ref operand, .. } => operand.get_location(),
+            Ast::NamedArgument { ref name_token, .. } => name_token.get_location(),
+            Ast::StructValue {
+                ref open_parenthesis_token,
+                ..
+            } => open_parenthesis_token.get_location(),
             Ast::StructType {
                 ref open_parenthesis_token,
                 ..
@@ -115,10 +243,18 @@ impl<'filepath, 'source> GetLocation<'filepath> for Ast<'filepath, 'source> {
                 ref open_square_bracket_token,
                 ..
             } => open_square_bracket_token.get_location(),
+            Ast::DynamicArrayType {
+                ref open_square_bracket_token,
+                ..
+            } => open_square_bracket_token.get_location(),
+            Ast::PointerType {
+                ref caret_token, ..
+            } => caret_token.get_location(),
             Ast::MultipointerType {
                 ref open_square_bracket_token,
                 ..
             } => open_square_bracket_token.get_location(),
+            Ast::Error { location, .. } => location,
         }
     }
 
@@ -140,14 +276,44 @@ impl<'filepath, 'source> GetLocation<'filepath> for Ast<'filepath, 'source> {
                 }
             }
             Ast::Name { ref name_token } => name_token.get_end_location(),
+            Ast::Integer { ref integer_token } => integer_token.get_end_location(),
             Ast::ParenthesisedExpression {
                 ref close_parenthesis_token,
                 ..
             } => close_parenthesis_token.get_end_location(),
+            Ast::Unary { ref operand, .. } => operand.get_end_location(),
+            Ast::AddressOf { ref operand, .. } => operand.get_end_location(),
+            Ast::Dereference {
+                ref caret_token, ..
+            } => caret_token.get_end_location(),
+            Ast::Binary { ref right, .. } => right.get_end_location(),
+            Ast::Assignment { ref value, .. } => value.get_end_location(),
+            Ast::If {
+                ref then_expression,
+                ref else_expression,
+                ..
+            } => else_expression
+                .as_ref()
+                .unwrap_or(then_expression)
+                .get_end_location(),
+            Ast::While { ref body, .. } => body.get_end_location(),
+            Ast::Goto { ref name_token, .. } => name_token.get_end_location(),
             Ast::MemberAccess {
                 ref member_name_token,
                 ..
             } => member_name_token.get_end_location(),
+            Ast::MemberIndex {
+                ref close_parenthesis_token,
+                ..
+            } => close_parenthesis_token.get_end_location(),
+            Ast::Index {
+                ref close_square_bracket_token,
+                ..
+            } => close_square_bracket_token.get_end_location(),
+            Ast::Slice {
+                ref close_square_bracket_token,
+                ..
+            } => close_square_bracket_token.get_end_location(),
             Ast::Procedure { ref body, .. } => body.get_end_location(),
             Ast::ProcedureType {
                 ref return_type, ..
@@ -156,13 +322,21 @@ impl<'filepath, 'source> GetLocation<'filepath> for Ast<'filepath, 'source> {
                 ref close_parenthesis_token,
                 ..
             } => close_parenthesis_token.get_end_location(),
+            Ast::NamedArgument { ref value, .. } => value.get_end_location(),
+            Ast::StructValue {
+                ref close_parenthesis_token,
+                ..
+            } => close_parenthesis_token.get_end_location(),
             Ast::StructType {
                 ref close_parenthesis_token,
                 ..
             } => close_parenthesis_token.get_end_location(),
             Ast::SliceType { ref operand, .. } => operand.get_end_location(),
             Ast::ArrayType { ref operand, .. } => operand.get_end_location(),
+            Ast::DynamicArrayType { ref operand, .. } => operand.get_end_location(),
+            Ast::PointerType { ref operand, .. } => operand.get_end_location(),
             Ast::MultipointerType { ref operand, .. } => operand.get_end_location(),
+            Ast::Error { end_location, .. } => end_location,
         }
     }
 }
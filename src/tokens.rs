@@ -12,12 +12,18 @@ pub enum TokenKind<'source> {
     Newline,
     #[display(fmt = "{_0}")]
     Name(&'source str),
+    #[display(fmt = "{_0}")]
+    Integer(u128),
     #[display(fmt = "->")]
     RightArrow,
+    #[display(fmt = "<->")]
+    LeftRightArrow,
     #[display(fmt = ":")]
     Colon,
     #[display(fmt = ";")]
     Semicolon,
+    #[display(fmt = "?")]
+    QuestionMark,
     #[display(fmt = "+")]
     Plus,
     #[display(fmt = "-")]
@@ -26,12 +32,48 @@ pub enum TokenKind<'source> {
     Asterisk,
     #[display(fmt = "/")]
     Slash,
+    #[display(fmt = "+=")]
+    PlusEqual,
+    #[display(fmt = "-=")]
+    MinusEqual,
+    #[display(fmt = "*=")]
+    AsteriskEqual,
+    #[display(fmt = "/=")]
+    SlashEqual,
+    #[display(fmt = "%")]
+    Percent,
+    #[display(fmt = "!")]
+    ExclamationMark,
+    #[display(fmt = "==")]
+    EqualEqual,
+    #[display(fmt = "!=")]
+    ExclamationMarkEqual,
+    #[display(fmt = "<")]
+    LessThan,
+    #[display(fmt = "<=")]
+    LessThanEqual,
+    #[display(fmt = ">")]
+    GreaterThan,
+    #[display(fmt = ">=")]
+    GreaterThanEqual,
+    #[display(fmt = "&&")]
+    AmpersandAmpersand,
+    #[display(fmt = "&")]
+    Ampersand,
+    #[display(fmt = "||")]
+    PipePipe,
+    #[display(fmt = "|")]
+    Pipe,
     #[display(fmt = "^")]
     Caret,
     #[display(fmt = ".")]
     Period,
+    #[display(fmt = "..")]
+    PeriodPeriod,
     #[display(fmt = ",")]
     Comma,
+    #[display(fmt = "$")]
+    Dollar,
     #[display(fmt = "=")]
     Equal,
     #[display(fmt = "(")]
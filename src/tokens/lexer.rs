@@ -1,4 +1,4 @@
-use super::{SourceLocation, Token, TokenKind};
+use super::{GetLocation, SourceLocation, Token, TokenKind};
 use derive_more::Display;
 use std::{iter::Peekable, str::CharIndices};
 
@@ -27,6 +27,10 @@ impl<'filepath, 'source> Lexer<'filepath, 'source> {
         self.location.filepath
     }
 
+    pub fn get_location(&self) -> SourceLocation<'filepath> {
+        self.location
+    }
+
     pub fn peek_char(&mut self) -> Option<char> {
         Some(self.chars.peek()?.1)
     }
@@ -63,15 +67,74 @@ impl<'filepath, 'source> Iterator for Lexer<'filepath, 'source> {
                         TokenKind::RightArrow
                     }
 
+                    '<' if self.source[self.location.position..].starts_with("->") => {
+                        self.next_char();
+                        self.next_char();
+                        TokenKind::LeftRightArrow
+                    }
+
+                    '=' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::EqualEqual
+                    }
+                    '!' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::ExclamationMarkEqual
+                    }
+                    '<' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::LessThanEqual
+                    }
+                    '>' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::GreaterThanEqual
+                    }
+                    '&' if self.peek_char() == Some('&') => {
+                        self.next_char();
+                        TokenKind::AmpersandAmpersand
+                    }
+                    '|' if self.peek_char() == Some('|') => {
+                        self.next_char();
+                        TokenKind::PipePipe
+                    }
+                    '.' if self.peek_char() == Some('.') => {
+                        self.next_char();
+                        TokenKind::PeriodPeriod
+                    }
+                    '+' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::PlusEqual
+                    }
+                    '-' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::MinusEqual
+                    }
+                    '*' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::AsteriskEqual
+                    }
+                    '/' if self.peek_char() == Some('=') => {
+                        self.next_char();
+                        TokenKind::SlashEqual
+                    }
+
                     ':' => TokenKind::Colon,
                     ';' => TokenKind::Semicolon,
+                    '?' => TokenKind::QuestionMark,
                     '+' => TokenKind::Plus,
                     '-' => TokenKind::Minus,
                     '*' => TokenKind::Asterisk,
                     '/' => TokenKind::Slash,
+                    '%' => TokenKind::Percent,
+                    '!' => TokenKind::ExclamationMark,
+                    '<' => TokenKind::LessThan,
+                    '>' => TokenKind::GreaterThan,
+                    '|' => TokenKind::Pipe,
+                    '&' => TokenKind::Ampersand,
                     '^' => TokenKind::Caret,
                     '.' => TokenKind::Period,
                     ',' => TokenKind::Comma,
+                    '$' => TokenKind::Dollar,
                     '=' => TokenKind::Equal,
                     '(' => TokenKind::OpenParenthesis,
                     ')' => TokenKind::CloseParenthesis,
@@ -81,6 +144,7 @@ impl<'filepath, 'source> Iterator for Lexer<'filepath, 'source> {
                     ']' => TokenKind::CloseSquareBracket,
 
                     c if c.is_alphabetic() || c == '_' => {
+                        #[allow(clippy::while_let_loop)]
                         loop {
                             let Some(c) = self.peek_char() else { break };
                             if c.is_alphanumeric() || c == '_' {
@@ -95,9 +159,76 @@ impl<'filepath, 'source> Iterator for Lexer<'filepath, 'source> {
                         )
                     }
 
+                    c if c.is_ascii_digit() => {
+                        let base = match (c, self.peek_char()) {
+                            ('0', Some('x')) => {
+                                self.next_char();
+                                16
+                            }
+                            ('0', Some('b')) => {
+                                self.next_char();
+                                2
+                            }
+                            _ => 10,
+                        };
+
+                        let mut value: u128 = if base == 10 {
+                            c.to_digit(10).unwrap().into()
+                        } else {
+                            0
+                        };
+                        let mut has_digits = base == 10;
+                        let mut too_large = false;
+                        while let Some(c) = self.peek_char() {
+                            if c == '_' {
+                                self.next_char();
+                                continue;
+                            }
+                            if !c.is_alphanumeric() {
+                                break;
+                            }
+
+                            let digit_location = self.location;
+                            self.next_char();
+                            let Some(digit) = c.to_digit(base) else {
+                                return Some(Err(LexerError::InvalidDigit {
+                                    location: digit_location,
+                                    end_location: self.location,
+                                    digit: c,
+                                    base,
+                                }));
+                            };
+                            has_digits = true;
+                            match value
+                                .checked_mul(u128::from(base))
+                                .and_then(|value| value.checked_add(digit.into()))
+                            {
+                                Some(new_value) => value = new_value,
+                                None => too_large = true,
+                            }
+                        }
+
+                        if !has_digits {
+                            return Some(Err(LexerError::ExpectedDigits {
+                                location: start_location,
+                                end_location: self.location,
+                                base,
+                            }));
+                        }
+                        if too_large {
+                            return Some(Err(LexerError::IntegerTooLarge {
+                                location: start_location,
+                                end_location: self.location,
+                            }));
+                        }
+
+                        TokenKind::Integer(value)
+                    }
+
                     c => {
                         return Some(Err(LexerError::UnexpectedCharacter {
                             location: start_location,
+                            end_location: self.location,
                             unexpected_character: c,
                         }));
                     }
@@ -111,9 +242,48 @@ impl<'filepath, 'source> Iterator for Lexer<'filepath, 'source> {
 
 #[derive(Debug, Display)]
 pub enum LexerError<'filepath> {
-    #[display(fmt = "{location}: Unexpected character: {unexpected_character:?}")]
+    #[display(fmt = "Unexpected character: {unexpected_character:?}")]
     UnexpectedCharacter {
         location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
         unexpected_character: char,
     },
+    #[display(fmt = "Invalid digit {digit:?} for a base {base} integer")]
+    InvalidDigit {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        digit: char,
+        base: u32,
+    },
+    #[display(fmt = "Expected at least one digit for a base {base} integer")]
+    ExpectedDigits {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+        base: u32,
+    },
+    #[display(fmt = "Integer literal is too large")]
+    IntegerTooLarge {
+        location: SourceLocation<'filepath>,
+        end_location: SourceLocation<'filepath>,
+    },
+}
+
+impl<'filepath> GetLocation<'filepath> for LexerError<'filepath> {
+    fn get_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            LexerError::UnexpectedCharacter { location, .. }
+            | LexerError::InvalidDigit { location, .. }
+            | LexerError::ExpectedDigits { location, .. }
+            | LexerError::IntegerTooLarge { location, .. } => location,
+        }
+    }
+
+    fn get_end_location(&self) -> SourceLocation<'filepath> {
+        match *self {
+            LexerError::UnexpectedCharacter { end_location, .. }
+            | LexerError::InvalidDigit { end_location, .. }
+            | LexerError::ExpectedDigits { end_location, .. }
+            | LexerError::IntegerTooLarge { end_location, .. } => end_location,
+        }
+    }
 }
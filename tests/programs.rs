@@ -0,0 +1,274 @@
+use std::process::Command;
+
+fn program_path(name: &str) -> String {
+    format!("{}/tests/programs/{name}.nkl", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Runs `tests/programs/{name}.nkl` with the `run` command, returns the exit code and what was written to stderr
+fn run(name: &str) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_main"))
+        .arg("run")
+        .arg(program_path(name))
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+fn expect_exit_code(name: &str, expected: i32) {
+    let (code, stderr) = run(name);
+    assert_eq!(code, expected, "{stderr}");
+}
+
+fn expect_error(name: &str, message: &str) {
+    let (code, stderr) = run(name);
+    assert_eq!(code, 1);
+    assert!(stderr.contains(message), "{stderr}");
+}
+
+#[test]
+fn arithmetic() {
+    expect_exit_code("arithmetic", 12);
+}
+
+#[test]
+fn casts_wrap() {
+    expect_exit_code("casts_wrap", 18);
+}
+
+#[test]
+fn overflow() {
+    expect_error("overflow", "The result does not fit in the type u8");
+}
+
+#[test]
+fn goto() {
+    expect_exit_code("goto", 45);
+}
+
+#[test]
+fn pointers() {
+    expect_exit_code("pointers", 46);
+}
+
+#[test]
+fn null_dereference() {
+    expect_error(
+        "null_dereference",
+        "Attempted to dereference a null pointer",
+    );
+}
+
+#[test]
+fn slices() {
+    expect_exit_code("slices", 109);
+}
+
+#[test]
+fn generics() {
+    expect_exit_code("generics", 31);
+}
+
+#[test]
+fn recursion() {
+    expect_exit_code("recursion", 182);
+}
+
+#[test]
+fn named_and_default_arguments() {
+    expect_exit_code("named_and_default_arguments", 116);
+}
+
+#[test]
+fn missing_struct_argument() {
+    expect_error(
+        "missing_struct_argument",
+        "There is no argument for 'x', which does not have a default value",
+    );
+}
+
+#[test]
+fn no_default_value() {
+    expect_error(
+        "no_default_value",
+        "The type (a: [2]never, b: int) has no default value, so a value must be given",
+    );
+}
+
+#[test]
+fn dynamic_arrays() {
+    expect_exit_code("dynamic_arrays", 55);
+}
+
+#[test]
+fn resize_without_default() {
+    expect_error(
+        "resize_without_default",
+        "The type never has no default value, so a value must be given",
+    );
+}
+
+#[test]
+fn unions() {
+    expect_exit_code("unions", 15);
+}
+
+#[test]
+fn union_mismatch() {
+    expect_error(
+        "union_mismatch",
+        "Expected the union to hold the type int, but it holds the type bool",
+    );
+}
+
+#[test]
+fn union_without_value() {
+    expect_error(
+        "union_without_value",
+        "The type bool | int has no default value, so a value must be given",
+    );
+}
+
+#[test]
+fn conditional_call() {
+    expect_exit_code("conditional_call", 7);
+}
+
+#[test]
+fn compile_time_call_error() {
+    let (code, stderr) = run("compile_time_call_error");
+    assert_eq!(code, 1);
+    assert!(
+        stderr.contains("The call fails when it is evaluated at compile time: Division by zero"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("compile_time_call_error.nkl:5:10"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("This is where it fails"), "{stderr}");
+}
+
+#[test]
+fn short_circuit() {
+    expect_exit_code("short_circuit", 14);
+}
+
+#[test]
+fn while_loops() {
+    expect_exit_code("while_loops", 45);
+}
+
+#[test]
+fn parser_recovery() {
+    let (code, stderr) = run("parser_recovery");
+    assert_eq!(code, 1);
+    assert!(
+        stderr.contains("error: Unexpected token )\n --> ")
+            && stderr.contains("parser_recovery.nkl:2:14"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("error: Unexpected token {newline}\n --> ")
+            && stderr.contains("parser_recovery.nkl:3:13"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn diagnostic_rendering() {
+    let (code, stderr) = run("diagnostic_rendering");
+    assert_eq!(code, 1);
+    let path = program_path("diagnostic_rendering");
+    assert_eq!(
+        stderr,
+        format!(
+            "\
+error: The branches of the conditional have different types, int and bool
+ --> {path}:3:5
+  |
+3 |     flag ? 1 : {{
+  |     ^^^^^^^^^^^^
+4 |         false
+  |         ^^^^^
+5 |     }}
+  |     ^
+  |
+3 |     flag ? 1 : {{
+  |            - This has type int
+  |
+3 |     flag ? 1 : {{
+  |                -
+4 |         false
+  |         -----
+5 |     }}
+  |     - This has type bool
+
+"
+        )
+    );
+}
+
+#[test]
+fn struct_indexing() {
+    expect_exit_code("struct_indexing", 39);
+}
+
+#[test]
+fn single_member_struct() {
+    expect_exit_code("single_member_struct", 37);
+}
+
+#[test]
+fn procedure_types() {
+    expect_exit_code("procedure_types", 32);
+}
+
+#[test]
+fn parameter_inference() {
+    expect_exit_code("parameter_inference", 34);
+}
+
+#[test]
+fn uninferable_parameter() {
+    expect_error(
+        "uninferable_parameter",
+        "The type of the parameter 'x' cannot be inferred, because no procedure type is expected here",
+    );
+}
+
+#[test]
+fn missing_main() {
+    let (code, stderr) = run("missing_main");
+    assert_eq!(code, 1);
+    let path = program_path("missing_main");
+    assert_eq!(
+        stderr,
+        format!(
+            "\
+error: Expected a procedure called main
+ --> {path}:1:1
+  |
+1 | answer :: 42
+  | ^
+  = note: The program starts by calling `main :: (args: [][^]u8) -> int`
+
+"
+        )
+    );
+}
+
+#[test]
+fn dump_ir() {
+    let output = Command::new(env!("CARGO_BIN_EXE_main"))
+        .arg("dump_ir")
+        .arg(program_path("arithmetic"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Binary {"), "{stdout}");
+    assert!(stdout.contains("\nmain: "), "{stdout}");
+}